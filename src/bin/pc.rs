@@ -0,0 +1,84 @@
+use kurenai_tetrist::models::{
+    game_mode::{puzzle::Puzzle, GameMode},
+    pc_solver::PcSolver,
+    rules::Rules,
+    tetromino::TetrominoKind,
+};
+
+use std::env;
+use std::fs;
+use std::process;
+
+const USAGE: &str = "usage: pc [--height N] [--hold PIECE] <puzzles.txt>";
+
+fn main() {
+    let mut height = 4;
+    let mut hold = None;
+    let mut path = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--height" => {
+                height = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or_else(|| exit(USAGE));
+            }
+            "--hold" => {
+                let kind = args
+                    .next()
+                    .and_then(|value| TetrominoKind::parse(&value))
+                    .unwrap_or_else(|| exit(USAGE));
+                hold = Some(kind);
+            }
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => exit(USAGE),
+        }
+    }
+    let path = path.unwrap_or_else(|| exit(USAGE));
+
+    let text = fs::read_to_string(&path)
+        .unwrap_or_else(|err| exit(&format!("failed to read {}: {}", path, err)));
+    let puzzles = Puzzle::parse_pack(&text).unwrap_or_else(|err| exit(&err));
+    let solver = PcSolver::new(Rules::modern());
+    let mut unsolved = 0;
+    for puzzle in puzzles.iter() {
+        println!("{}", puzzle.title());
+        let queue = puzzle.queue().unwrap_or_default();
+        match solver.solve(&puzzle.initial_field(), &queue, hold, height) {
+            Some(solution) => {
+                for (idx, placement) in solution.iter().enumerate() {
+                    let cells: Vec<_> = placement
+                        .blocks()
+                        .iter()
+                        .map(|block| format!("{},{}", block.x(), block.y()))
+                        .collect();
+                    let path: Vec<_> = placement
+                        .path()
+                        .iter()
+                        .map(|action| format!("{:?}", action))
+                        .collect();
+                    println!(
+                        "{:>3} {} [{}] {}",
+                        idx + 1,
+                        placement.kind().name(),
+                        cells.join(" "),
+                        path.join(" ")
+                    );
+                }
+            }
+            None => {
+                println!("  no perfect clear within {} lines", height);
+                unsolved += 1;
+            }
+        }
+    }
+    if unsolved > 0 {
+        process::exit(1);
+    }
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
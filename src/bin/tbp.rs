@@ -0,0 +1,100 @@
+use kurenai_tetrist::{
+    models::{
+        game::Game,
+        game_mode::{endless::Endless, marathon::Marathon, sprint::Sprint, GameMode},
+    },
+    tbp::Frontend,
+};
+
+use serde_json::Value;
+
+use std::env;
+use std::io::{BufRead, BufReader, Lines, Write};
+use std::process::{self, ChildStdin, ChildStdout, Command, Stdio};
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let mode: Box<dyn GameMode> = if args.first().map(String::as_str) == Some("--mode") {
+        let mode = args.get(1).cloned().unwrap_or_default();
+        args.drain(..2.min(args.len()));
+        match mode.as_str() {
+            "marathon" => Box::new(Marathon::new(None)),
+            "sprint" => Box::new(Sprint::new(None)),
+            "endless" => Box::new(Endless),
+            _ => exit(&format!("unknown mode `{}`", mode)),
+        }
+    } else {
+        Box::new(Sprint::new(None))
+    };
+    if args.is_empty() {
+        exit("usage: tbp [--mode marathon|sprint|endless] <bot> [args...]");
+    }
+
+    let mut bot = Command::new(&args[0])
+        .args(&args[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|err| exit(&format!("failed to start `{}`: {}", args[0], err)));
+    let mut stdin = bot.stdin.take().unwrap();
+    let mut stdout = BufReader::new(bot.stdout.take().unwrap()).lines();
+
+    let info = receive(&mut stdout, "info");
+    eprintln!("bot: {} {}", info["name"], info["version"]);
+
+    let mut frontend = Frontend::new(Game::new(mode));
+    send(&mut stdin, &frontend.rules());
+    receive(&mut stdout, "ready");
+    let start = frontend.start();
+    send(&mut stdin, &start);
+
+    while !frontend.game().phase().is_finished() {
+        send(&mut stdin, &frontend.suggest());
+        let suggestion = receive(&mut stdout, "suggestion");
+        match frontend.play(&suggestion) {
+            Ok(messages) => {
+                for message in messages.iter() {
+                    send(&mut stdin, message);
+                }
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                break;
+            }
+        }
+    }
+    send(&mut stdin, &frontend.stop());
+    send(&mut stdin, &frontend.quit());
+    let _ = bot.wait();
+
+    let game = frontend.game();
+    for line in game.mode().result(game.stats()) {
+        println!("{}", line);
+    }
+    println!("PIECES {}", game.stats().pieces());
+}
+
+fn send(stdin: &mut ChildStdin, message: &Value) {
+    writeln!(stdin, "{}", message).unwrap_or_else(|err| exit(&format!("bot closed: {}", err)));
+}
+
+fn receive(stdout: &mut Lines<BufReader<ChildStdout>>, expected: &str) -> Value {
+    loop {
+        let line = match stdout.next() {
+            Some(Ok(line)) => line,
+            _ => exit("bot closed its output"),
+        };
+        let message: Value =
+            serde_json::from_str(&line).unwrap_or_else(|err| exit(&format!("{}: {}", err, line)));
+        match message["type"].as_str() {
+            Some(kind) if kind == expected => return message,
+            Some("error") => exit(&format!("bot error: {}", message["reason"])),
+            _ => eprintln!("ignored: {}", line),
+        }
+    }
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1)
+}
@@ -0,0 +1,199 @@
+use kurenai_tetrist::models::{
+    ai::{evaluator::Weights, heuristic::Heuristic, Ai},
+    game::Game,
+    game_mode::endless::Endless,
+    rng::Rng,
+    rules::Rules,
+    tetromino_factory::Randomizer,
+};
+
+use std::env;
+use std::fs;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+const FEATURES: [&str; 7] = [
+    "height",
+    "holes",
+    "bumpiness",
+    "wells",
+    "row_transitions",
+    "column_transitions",
+    "lines",
+];
+
+struct Options {
+    generations: usize,
+    population: usize,
+    games: u32,
+    pieces: i32,
+    threads: usize,
+    seed: u32,
+    output: String,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Fitness {
+    lines: f64,
+    score: f64,
+}
+
+fn main() {
+    let options = parse_options();
+    let mut rng = Rng::new(options.seed);
+    let mut mean = Weights::default().to_vec();
+    let mut deviation = vec![0.5; FEATURES.len()];
+    let mut best: Option<(Vec<f64>, Fitness)> = None;
+
+    for generation in 0..options.generations {
+        let candidates: Vec<Vec<f64>> = (0..options.population)
+            .map(|_| {
+                mean.iter()
+                    .zip(deviation.iter())
+                    .map(|(mean, deviation)| mean + deviation * gaussian(&mut rng))
+                    .collect()
+            })
+            .collect();
+        let fitnesses = evaluate(&candidates, &options);
+
+        let mut ranked: Vec<_> = candidates.into_iter().zip(fitnesses).collect();
+        ranked.sort_by(|(_, a), (_, b)| b.lines.partial_cmp(&a.lines).unwrap());
+        for (weights, fitness) in ranked.iter() {
+            println!(
+                "gen {:>3} lines {:>8.1} score {:>10.1} {}",
+                generation,
+                fitness.lines,
+                fitness.score,
+                format_weights(weights)
+            );
+        }
+
+        let elites = &ranked[..(options.population / 4).max(1)];
+        for idx in 0..FEATURES.len() {
+            let values: Vec<f64> = elites.iter().map(|(weights, _)| weights[idx]).collect();
+            let average = values.iter().sum::<f64>() / values.len() as f64;
+            let variance = values
+                .iter()
+                .map(|value| (value - average).powi(2))
+                .sum::<f64>()
+                / values.len() as f64;
+            mean[idx] = average;
+            deviation[idx] = variance.sqrt() + 0.1 / (generation + 1) as f64;
+        }
+
+        let (weights, fitness) = &ranked[0];
+        let is_better = match &best {
+            Some((_, best)) => fitness.lines > best.lines,
+            None => true,
+        };
+        if is_better {
+            best = Some((weights.clone(), *fitness));
+        }
+        let (weights, fitness) = best.as_ref().unwrap();
+        eprintln!(
+            "gen {:>3} best lines {:.1} score {:.1}",
+            generation, fitness.lines, fitness.score
+        );
+        let content = FEATURES
+            .iter()
+            .zip(weights.iter())
+            .map(|(name, weight)| format!("{} {}\n", name, weight))
+            .collect::<String>();
+        fs::write(&options.output, content).unwrap_or_else(|err| {
+            eprintln!("failed to write {}: {}", options.output, err);
+            process::exit(1);
+        });
+    }
+}
+
+fn evaluate(candidates: &[Vec<f64>], options: &Options) -> Vec<Fitness> {
+    let results = Mutex::new(vec![Fitness::default(); candidates.len()]);
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..options.threads {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::SeqCst);
+                if idx >= candidates.len() {
+                    break;
+                }
+                let mut fitness = Fitness::default();
+                for seed in 1..=options.games {
+                    let (lines, score) = play(&candidates[idx], seed, options.pieces);
+                    fitness.lines += lines as f64 / options.games as f64;
+                    fitness.score += score as f64 / options.games as f64;
+                }
+                results.lock().unwrap()[idx] = fitness;
+            });
+        }
+    });
+    results.into_inner().unwrap()
+}
+
+fn play(weights: &[f64], seed: u32, pieces: i32) -> (i32, i32) {
+    let weights = Weights::new(
+        weights[0], weights[1], weights[2], weights[3], weights[4], weights[5], weights[6],
+    );
+    let rules = Rules::modern().with_randomizer(Randomizer::SeededBag(seed));
+    let mut game = Game::with_rules(Box::new(Endless), rules);
+    let mut ai = Ai::new(Box::new(Heuristic::new(rules, weights)));
+    while !game.phase().is_finished() && *game.stats().pieces() < pieces {
+        ai.step(&mut game);
+    }
+    (*game.stats().lines(), *game.stats().score())
+}
+
+fn gaussian(rng: &mut Rng) -> f64 {
+    let u1 = (rng.next_u32() as f64 + 1.0) / (u32::MAX as f64 + 2.0);
+    let u2 = rng.next_u32() as f64 / u32::MAX as f64;
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+fn format_weights(weights: &[f64]) -> String {
+    weights
+        .iter()
+        .map(|weight| format!("{:>7.3}", weight))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_options() -> Options {
+    let mut options = Options {
+        generations: 10,
+        population: 32,
+        games: 4,
+        pieces: 500,
+        threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+        seed: 1,
+        output: "weights.txt".to_string(),
+    };
+    let args: Vec<String> = env::args().skip(1).collect();
+    for pair in args.chunks(2) {
+        let value = pair.get(1).map(String::as_str).unwrap_or_default();
+        let parsed = match pair[0].as_str() {
+            "--generations" => value.parse().map(|value| options.generations = value),
+            "--population" => value.parse().map(|value| options.population = value),
+            "--games" => value.parse().map(|value| options.games = value),
+            "--pieces" => value.parse().map(|value| options.pieces = value),
+            "--threads" => value.parse().map(|value| options.threads = value),
+            "--seed" => value.parse().map(|value| options.seed = value),
+            "--output" => {
+                options.output = value.to_string();
+                Ok(())
+            }
+            _ => {
+                eprintln!(
+                    "usage: tune [--generations N] [--population N] [--games N] [--pieces N] \
+                     [--threads N] [--seed N] [--output FILE]"
+                );
+                process::exit(1);
+            }
+        };
+        if parsed.is_err() {
+            eprintln!("invalid value `{}` for {}", value, pair[0]);
+            process::exit(1);
+        }
+    }
+    options
+}
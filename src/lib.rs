@@ -1,10 +1,23 @@
-mod models;
+pub mod models;
+mod storage;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tbp;
 
 use crate::models::{
-    count::Count,
-    field::Field,
-    tetromino::{MoveDirection, RotateDirection, Tetromino},
-    tetromino_factory::TetrominoFactory,
+    ai::{beam_search::BeamSearch, evaluator::Weights, Ai},
+    fumen::{Fumen, Page},
+    game::Game,
+    game_mode::{
+        big::Big, classic::Classic, dig::Dig, drill::Drill, endless::Endless, marathon::Marathon,
+        master::Master, puzzle::Puzzle, roll::Roll, sprint::Sprint, ultra::Ultra, zen::Zen,
+        GameMode,
+    },
+    hint::Hint,
+    hud::Hud,
+    input::{Action, Input},
+    menu::Menu,
+    move_generator::Placement,
+    rotation_system::RotationSystem,
 };
 
 use kurenai::game_loop;
@@ -12,107 +25,223 @@ use kurenai::game_service::GameService;
 use kurenai::key_event::KeyEvent;
 use kurenai::{canvas, image};
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::ops::Deref;
 use std::rc::Rc;
 
 use wasm_bindgen::prelude::*;
 
+const MODES: [&str; 14] = [
+    "MARATHON",
+    "SPRINT",
+    "ULTRA",
+    "DIG",
+    "CLASSIC",
+    "MASTER",
+    "PUZZLE",
+    "DRILL",
+    "BIG",
+    "FADING",
+    "INVISIBLE",
+    "ENDLESS",
+    "ZEN",
+    "DEMO",
+];
+const MODE_TITLE: &str = "SELECT MODE";
+const PUZZLE_TITLE: &str = "SELECT PUZZLE";
+const DRILL_TITLE: &str = "SELECT OPENER";
+const DEMO_WIDTH: usize = 32;
+const DEMO_DEPTH: usize = 5;
+const DEMO_BUDGET: f64 = 8.0;
+
 struct TetrisGameService {
-    count: RefCell<Count>,
-    field: RefCell<Field>,
-    tetromino_factory: RefCell<TetrominoFactory>,
-    tetromino: RefCell<Box<dyn Tetromino>>,
+    game: RefCell<Game>,
+    hud: RefCell<Hud>,
+    menu: RefCell<Option<Menu>>,
+    rotation_system: Cell<Option<RotationSystem>>,
+    puzzles: Vec<Puzzle>,
+    drills: Vec<Drill>,
+    ai: RefCell<Option<Ai>>,
+    hint: RefCell<Option<Hint>>,
+    key_event: RefCell<KeyEvent>,
     image: Rc<web_sys::HtmlImageElement>,
 }
 
 impl GameService for TetrisGameService {
     fn key_event(&self, key_event: &KeyEvent) {
-        if !self.count.borrow().beat(4) {
+        let last_key_event = self.key_event.replace(*key_event);
+        let mut menu = self.menu.borrow_mut();
+
+        if let Some(opened) = menu.as_mut() {
+            if key_event.arrow_up() && !last_key_event.arrow_up() {
+                opened.up();
+            } else if key_event.arrow_down() && !last_key_event.arrow_down() {
+                opened.down();
+            } else if (key_event.arrow_left() && !last_key_event.arrow_left())
+                || (key_event.arrow_right() && !last_key_event.arrow_right())
+            {
+                self.rotation_system.set(match self.rotation_system.get() {
+                    None => Some(RotationSystem::Srs),
+                    Some(RotationSystem::Srs) => Some(RotationSystem::Ars),
+                    Some(RotationSystem::Ars) => None,
+                });
+            } else if key_event.enter() && !last_key_event.enter() {
+                let cursor = *opened.cursor();
+                if opened.title() == PUZZLE_TITLE {
+                    self.start(Box::new(self.puzzles[cursor].clone()));
+                    *menu = None;
+                } else if opened.title() == DRILL_TITLE {
+                    self.start(Box::new(self.drills[cursor].clone()));
+                    *menu = None;
+                } else if MODES[cursor] == "PUZZLE" {
+                    *menu = Some(self.build_puzzle_menu());
+                } else if MODES[cursor] == "DRILL" {
+                    *menu = Some(self.build_drill_menu());
+                } else if MODES[cursor] == "DEMO" {
+                    self.start(Box::new(Marathon::new(None)));
+                    let rules = *self.game.borrow().rules();
+                    let bot = BeamSearch::new(
+                        rules,
+                        Weights::default(),
+                        DEMO_WIDTH,
+                        DEMO_DEPTH,
+                        DEMO_BUDGET,
+                        Self::now,
+                    );
+                    *self.ai.borrow_mut() = Some(Ai::new(Box::new(bot)));
+                    *menu = None;
+                } else {
+                    self.start(Self::build_mode(cursor));
+                    *menu = None;
+                }
+            }
             return;
         }
 
-        let mut tetromino = self.tetromino.borrow_mut();
-        let field = self.field.borrow();
-
-        if key_event.arrow_left() {
-            let blocks = tetromino.dry_move(MoveDirection::Left);
-            if field.is_vacant(&blocks) {
-                tetromino.move_(MoveDirection::Left);
-            }
-        } else if key_event.arrow_right() {
-            let blocks = tetromino.dry_move(MoveDirection::Right);
-            if field.is_vacant(&blocks) {
-                tetromino.move_(MoveDirection::Right);
-            }
-        } else if key_event.arrow_down() {
-            let blocks = tetromino.dry_move(MoveDirection::Down);
-            if field.is_vacant(&blocks) {
-                tetromino.move_(MoveDirection::Down);
-            }
-        } else if key_event.key_z() {
-            let blocks = tetromino.dry_rotate(RotateDirection::Left);
-            if field.is_vacant(&blocks) {
-                tetromino.rotate(RotateDirection::Left);
-            }
-        } else if key_event.key_x() {
-            let blocks = tetromino.dry_rotate(RotateDirection::Right);
-            if field.is_vacant(&blocks) {
-                tetromino.rotate(RotateDirection::Right);
+        let mut game = self.game.borrow_mut();
+        if key_event.key_h() && !last_key_event.key_h() {
+            let mut hint = self.hint.borrow_mut();
+            *hint = match *hint {
+                Some(_) => None,
+                None => Some(Hint::new(*game.rules(), Weights::default())),
+            };
+        }
+        if key_event.key_e() && !last_key_event.key_e() {
+            if let Some(window) = web_sys::window() {
+                let _ = window
+                    .navigator()
+                    .clipboard()
+                    .write_text(&Self::export(&game));
             }
         }
-    }
-
-    fn update(&self) {
-        let mut count = self.count.borrow_mut();
-
-        count.add();
-        if !count.beat(16) {
+        let mut ai = self.ai.borrow_mut();
+        if (game.phase().is_finished() || ai.is_some())
+            && key_event.enter()
+            && !last_key_event.enter()
+        {
+            *ai = None;
+            *menu = Some(Self::build_menu());
             return;
         }
-
-        let mut field = self.field.borrow_mut();
-        let mut tetromino = self.tetromino.borrow_mut();
-
-        let blocks = tetromino.dry_move(MoveDirection::Down);
-        if field.is_vacant(&blocks) {
-            tetromino.move_(MoveDirection::Down);
+        if game.phase().is_finished() {
             return;
         }
+        let input = match ai.as_mut() {
+            Some(ai) => ai.input(&mut game),
+            None => Self::input(key_event),
+        };
+        game.key_event(input);
+    }
 
-        if Field::can_fix(&blocks) {
-            let blocks = tetromino.blocks();
-            field.fix_blocks(blocks);
-            field.clear_blocks();
-        } else {
-            // game over
+    fn update(&self) {
+        if self.menu.borrow().is_some() {
+            return;
         }
 
-        let mut tetromino_factory = self.tetromino_factory.borrow_mut();
-        *tetromino = tetromino_factory.pick_tetromino();
-        if !field.is_vacant(&tetromino.blocks()) {
-            // game over
+        let mut game = self.game.borrow_mut();
+
+        let was_finished = game.phase().is_finished();
+        game.update();
+        if !was_finished && game.phase().is_finished() && self.ai.borrow().is_none() {
+            Self::save_record(&game);
         }
     }
 
     fn draw(&self, context: &web_sys::CanvasRenderingContext2d) {
-        context.clear_rect(0.0, 0.0, 320.0, 640.0);
+        context.clear_rect(0.0, 0.0, 512.0, 640.0);
 
         let image = self.image();
+        let hud = self.hud.borrow();
+
+        if let Some(menu) = self.menu.borrow().as_ref() {
+            hud.draw_menu(context, menu, self.rotation_system.get().as_ref());
+            return;
+        }
+
+        let mut game = self.game.borrow_mut();
 
         let blocks_to_draw = {
             let mut blocks_to_draw = Vec::new();
 
-            let field = self.field.borrow();
-            let tetromino = self.tetromino.borrow();
+            let phase = game.phase();
+            let field = game.field();
+            let visibility = game.mode().visibility();
+            let frames = *game.stats().frames();
 
-            blocks_to_draw.append(&mut field.blocks());
-            blocks_to_draw.append(&mut tetromino.blocks());
+            for block in field.blocks() {
+                if phase.is_flashing(*block.y()) {
+                    continue;
+                }
+                let alpha = match field.locked_at(&block) {
+                    Some(locked_at) if !phase.is_finished() => visibility.alpha(frames - locked_at),
+                    _ => 1.0,
+                };
+                blocks_to_draw.push((block, alpha));
+            }
+            if phase.is_falling() {
+                for block in game.tetromino().blocks() {
+                    blocks_to_draw.push((block, 1.0));
+                }
+            }
             blocks_to_draw
         };
 
-        for block in blocks_to_draw.iter() {
-            block.draw(context, image);
+        if game.phase().is_falling() {
+            context.set_global_alpha(0.3);
+            for block in game.mode().target() {
+                block.draw(context, image);
+            }
+        }
+        for (block, alpha) in blocks_to_draw.iter() {
+            if *alpha > 0.0 {
+                context.set_global_alpha(*alpha);
+                block.draw(context, image);
+            }
+        }
+        context.set_global_alpha(1.0);
+        if let Some(hint) = self.hint.borrow_mut().as_mut() {
+            for block in hint.update(&mut game) {
+                block.draw_outline(context);
+            }
+        }
+
+        let preview = *game.rules().preview();
+        let next = game.next_kinds(preview);
+        let status = game.mode().status(game.stats());
+        hud.draw(
+            context,
+            image,
+            game.stats(),
+            game.hold().tetromino(),
+            &next,
+            &status,
+        );
+        if game.phase().is_finished() {
+            let mut result = game.mode().result(game.stats());
+            result.push(format!("FINESSE {}", game.stats().faults()));
+            result.push(String::new());
+            result.push("PRESS ENTER".to_string());
+            hud.draw_result(context, &result);
         }
     }
 }
@@ -123,14 +252,20 @@ impl TetrisGameService {
             let bytes = include_bytes!("./assets/image.gif");
             image::create_new_html_image_element(&bytes.to_vec(), "gif")
         };
-        let field = Field::new(vec![vec![None; 10]; 24]);
-        let mut tetromino_factory = TetrominoFactory::new();
-        let tetromino = tetromino_factory.pick_tetromino();
+        let game = Game::new(Box::new(Endless));
+        let hud = Hud::new(game.mode().layout(), RotationSystem::Srs);
         Self {
-            count: RefCell::new(Count::new(0)),
-            field: RefCell::new(field),
-            tetromino_factory: RefCell::new(tetromino_factory),
-            tetromino: RefCell::new(tetromino),
+            game: RefCell::new(game),
+            hud: RefCell::new(hud),
+            menu: RefCell::new(Some(Self::build_menu())),
+            rotation_system: Cell::new(None),
+            puzzles: Puzzle::parse_pack(include_str!("./assets/puzzles.txt"))
+                .expect("Failed to parse puzzles"),
+            drills: Drill::parse_pack(include_str!("./assets/openers.txt"))
+                .expect("Failed to parse openers"),
+            ai: RefCell::new(None),
+            hint: RefCell::new(None),
+            key_event: RefCell::new(KeyEvent::new()),
             image: Rc::new(image),
         }
     }
@@ -138,6 +273,132 @@ impl TetrisGameService {
     fn image(&self) -> &web_sys::HtmlImageElement {
         self.image.deref()
     }
+
+    fn start(&self, mode: Box<dyn GameMode>) {
+        let rules = match self.rotation_system.get() {
+            Some(rotation_system) => mode.rules().with_rotation_system(rotation_system),
+            None => mode.rules(),
+        };
+        *self.hud.borrow_mut() = Hud::new(mode.layout(), *rules.rotation_system());
+        if let Some(hint) = self.hint.borrow_mut().as_mut() {
+            *hint = Hint::new(rules, Weights::default());
+        }
+        *self.game.borrow_mut() = Game::with_rules(mode, rules);
+        *self.ai.borrow_mut() = None;
+    }
+
+    fn build_menu() -> Menu {
+        Menu::new(
+            MODE_TITLE.to_string(),
+            MODES.iter().map(|mode| mode.to_string()).collect(),
+        )
+    }
+
+    fn build_puzzle_menu(&self) -> Menu {
+        Menu::new(
+            PUZZLE_TITLE.to_string(),
+            self.puzzles
+                .iter()
+                .map(|puzzle| puzzle.title().to_string())
+                .collect(),
+        )
+    }
+
+    fn build_drill_menu(&self) -> Menu {
+        Menu::new(
+            DRILL_TITLE.to_string(),
+            self.drills
+                .iter()
+                .map(|drill| drill.title().to_string())
+                .collect(),
+        )
+    }
+
+    fn build_mode(idx: usize) -> Box<dyn GameMode> {
+        match MODES[idx] {
+            "MARATHON" => Box::new(Marathon::new(storage::load("marathon"))),
+            "SPRINT" => Box::new(Sprint::with_clock(storage::load("sprint"), Self::now)),
+            "ULTRA" => Box::new(Ultra::new(storage::load("ultra"))),
+            "DIG" => Box::new(Dig::new(10, 18, 300, 30, Self::seed())),
+            "CLASSIC" => Box::new(Classic::new(0, Self::seed(), storage::load("classic"))),
+            "MASTER" => Box::new(Master::new(storage::load("master"))),
+            "BIG" => Box::new(Big::new(storage::load("big"))),
+            "FADING" => Box::new(Roll::fading(storage::load("fading"))),
+            "INVISIBLE" => Box::new(Roll::invisible(storage::load("invisible"))),
+            "ZEN" => Box::new(Zen),
+            _ => Box::new(Endless),
+        }
+    }
+
+    fn seed() -> u32 {
+        Self::now() as u32
+    }
+
+    fn now() -> f64 {
+        web_sys::window()
+            .and_then(|window| window.performance())
+            .map(|performance| performance.now())
+            .unwrap_or(0.0)
+    }
+
+    // Exports the field and the falling piece as a fumen for sharing.
+    fn export(game: &Game) -> String {
+        let tetromino = game.tetromino();
+        let piece = if game.phase().is_falling() {
+            Some(Placement::new(
+                tetromino.kind(),
+                tetromino.direction(),
+                tetromino.blocks(),
+                Vec::new(),
+            ))
+        } else {
+            None
+        };
+        let comment = game.mode().name().to_string();
+        Fumen::new(vec![Page::new(game.field().clone(), piece, comment)]).encode()
+    }
+
+    fn save_record(game: &Game) {
+        let mode = game.mode();
+        if let Some(record) = mode.record(game.stats()) {
+            let is_best = match storage::load(mode.name()) {
+                Some(best) => record.is_better_than(best),
+                None => true,
+            };
+            if is_best {
+                storage::save(mode.name(), record.value());
+            }
+        }
+    }
+
+    fn input(key_event: &KeyEvent) -> Input {
+        let mut actions = Vec::new();
+        if key_event.arrow_left() {
+            actions.push(Action::MoveLeft);
+        }
+        if key_event.arrow_right() {
+            actions.push(Action::MoveRight);
+        }
+        if key_event.arrow_down() {
+            actions.push(Action::SoftDrop);
+        }
+        if key_event.arrow_up() {
+            actions.push(Action::HardDrop);
+        }
+        if key_event.key_z() {
+            actions.push(Action::RotateLeft);
+        }
+        if key_event.key_x() {
+            actions.push(Action::RotateRight);
+        }
+        if key_event.key_a() {
+            actions.push(Action::RotateHalf);
+        }
+        if key_event.key_c() {
+            actions.push(Action::Hold);
+        }
+        Input::new(actions)
+    }
 }
 
 #[wasm_bindgen(start)]
@@ -1,5 +1,28 @@
+pub mod ai;
 pub mod block;
+pub mod clear;
+pub mod combo;
 pub mod count;
+pub mod delay;
 pub mod field;
+pub mod finesse;
+pub mod fumen;
+pub mod game;
+pub mod game_mode;
+pub mod gravity;
+pub mod hint;
+pub mod hold;
+pub mod hud;
+pub mod input;
+pub mod layout;
+pub mod menu;
+pub mod move_generator;
+pub mod pc_solver;
+pub mod phase;
+pub mod rng;
+pub mod rotation_system;
+pub mod rules;
+pub mod stats;
 pub mod tetromino;
 pub mod tetromino_factory;
+pub mod visibility;
@@ -0,0 +1,113 @@
+pub mod beam_search;
+pub mod evaluator;
+pub mod heuristic;
+
+use crate::models::{
+    game::Game,
+    input::{Action, Input},
+};
+
+use std::collections::VecDeque;
+
+pub trait Bot {
+    fn plan(&mut self, game: &mut Game) -> Vec<Action>;
+}
+
+pub struct Ai {
+    bot: Box<dyn Bot>,
+    plan: VecDeque<Action>,
+    piece: Option<i32>,
+    pressed: bool,
+}
+
+impl Ai {
+    pub fn new(bot: Box<dyn Bot>) -> Self {
+        Self {
+            bot,
+            plan: VecDeque::new(),
+            piece: None,
+            pressed: false,
+        }
+    }
+
+    pub fn input(&mut self, game: &mut Game) -> Input {
+        if !game.phase().is_falling() {
+            return Input::default();
+        }
+        let piece = *game.stats().pieces();
+        if self.piece != Some(piece) {
+            self.piece = Some(piece);
+            self.plan = self.bot.plan(game).into_iter().collect();
+            self.pressed = false;
+        }
+        // Every action is released for a frame so that it triggers exactly once.
+        if self.pressed {
+            self.pressed = false;
+            return Input::default();
+        }
+        match self.plan.pop_front() {
+            Some(action) => {
+                self.pressed = true;
+                Input::new(vec![action])
+            }
+            None => Input::default(),
+        }
+    }
+
+    // Plays the current piece without waiting for gravity, for headless games.
+    pub fn step(&mut self, game: &mut Game) {
+        let pieces = *game.stats().pieces();
+        for action in self.bot.plan(game) {
+            game.key_event(Input::new(vec![action]));
+            game.key_event(Input::default());
+        }
+        loop {
+            let phase = game.phase();
+            if phase.is_finished() || (phase.is_falling() && *game.stats().pieces() != pieces) {
+                break;
+            }
+            game.update();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        ai::{evaluator::Weights, heuristic::Heuristic},
+        game_mode::marathon::Marathon,
+        rules::Rules,
+    };
+
+    #[test]
+    fn test_input() {
+        let mut game = Game::new(Box::new(Marathon::new(None)));
+        let mut ai = Ai::new(Box::new(Heuristic::new(
+            Rules::modern(),
+            Weights::default(),
+        )));
+        for _ in 0..3000 {
+            let input = ai.input(&mut game);
+            game.key_event(input);
+            game.update();
+        }
+        assert!(!game.phase().is_finished());
+        assert!(*game.stats().pieces() > 50);
+        assert!(*game.stats().lines() > 10);
+    }
+
+    #[test]
+    fn test_step() {
+        let mut game = Game::new(Box::new(Marathon::new(None)));
+        let mut ai = Ai::new(Box::new(Heuristic::new(
+            Rules::modern(),
+            Weights::default(),
+        )));
+        for pieces in 1..=20 {
+            ai.step(&mut game);
+            assert_eq!(pieces, *game.stats().pieces());
+            assert!(game.phase().is_falling());
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use crate::models::{
+    ai::{
+        evaluator::{Features, Weights},
+        Bot,
+    },
+    clear::Clear,
+    field::Field,
+    game::Game,
+    input::Action,
+    move_generator::MoveGenerator,
+    rules::Rules,
+    tetromino::{Tetromino, TetrominoKind},
+};
+
+use std::collections::HashMap;
+
+const T_SPIN: f64 = 3.0;
+const PERFECT_CLEAR: f64 = 20.0;
+
+#[derive(Clone)]
+struct Node {
+    field: Field,
+    hold: Option<TetrominoKind>,
+    next: usize,
+    reward: f64,
+    score: f64,
+    path: Vec<Action>,
+}
+
+pub struct BeamSearch {
+    rules: Rules,
+    generator: MoveGenerator,
+    weights: Weights,
+    width: usize,
+    depth: usize,
+    budget: f64,
+    clock: fn() -> f64,
+}
+
+impl Bot for BeamSearch {
+    fn plan(&mut self, game: &mut Game) -> Vec<Action> {
+        let queue = game.next_kinds(*game.rules().preview());
+        let hold = game.hold().tetromino().copied();
+        let can_hold = game.hold().can_hold();
+        self.search(game.field(), game.tetromino(), hold, can_hold, &queue)
+    }
+}
+
+impl BeamSearch {
+    pub fn new(
+        rules: Rules,
+        weights: Weights,
+        width: usize,
+        depth: usize,
+        budget: f64,
+        clock: fn() -> f64,
+    ) -> Self {
+        Self {
+            rules,
+            generator: MoveGenerator::new(rules),
+            weights,
+            width,
+            depth,
+            budget,
+            clock,
+        }
+    }
+
+    pub fn search(
+        &self,
+        field: &Field,
+        tetromino: &dyn Tetromino,
+        hold: Option<TetrominoKind>,
+        can_hold: bool,
+        queue: &[TetrominoKind],
+    ) -> Vec<Action> {
+        let deadline = (self.clock)() + self.budget;
+        let root = Node {
+            field: field.clone(),
+            hold,
+            next: 0,
+            reward: 0.0,
+            score: 0.0,
+            path: Vec::new(),
+        };
+        let mut beam = self.expand(&root, tetromino, can_hold, queue, true);
+        for _ in 1..self.depth {
+            if (self.clock)() > deadline {
+                break;
+            }
+            let mut children = Vec::new();
+            for node in beam.iter() {
+                if let Some(kind) = queue.get(node.next) {
+                    let mut node = node.clone();
+                    node.next += 1;
+                    let tetromino = self.rules.build_tetromino(*kind);
+                    children.append(&mut self.expand(
+                        &node,
+                        tetromino.as_ref(),
+                        true,
+                        queue,
+                        false,
+                    ));
+                }
+                if (self.clock)() > deadline {
+                    break;
+                }
+            }
+            if children.is_empty() {
+                break;
+            }
+            beam = self.select(children);
+        }
+        beam.into_iter()
+            .next()
+            .map(|node| node.path)
+            .unwrap_or_default()
+    }
+}
+
+impl BeamSearch {
+    fn expand(
+        &self,
+        node: &Node,
+        tetromino: &dyn Tetromino,
+        can_hold: bool,
+        queue: &[TetrominoKind],
+        is_root: bool,
+    ) -> Vec<Node> {
+        let mut options = vec![(tetromino.clone_box(), node.hold, node.next, false)];
+        if *self.rules.hold() && can_hold {
+            let held = Some(tetromino.kind());
+            match node.hold {
+                Some(kind) => {
+                    options.push((self.rules.build_tetromino(kind), held, node.next, true))
+                }
+                None => {
+                    if let Some(kind) = queue.get(node.next) {
+                        let swapped = self.rules.build_tetromino(*kind);
+                        options.push((swapped, held, node.next + 1, true));
+                    }
+                }
+            }
+        }
+
+        let mut children = Vec::new();
+        for (tetromino, hold, next, held) in options {
+            for placement in self.generator.generate(&node.field, tetromino.as_ref()) {
+                if !Field::can_fix(placement.blocks()) {
+                    continue;
+                }
+                let t_spin = *placement.kind() == TetrominoKind::T
+                    && placement.is_rotated()
+                    && node.field.is_t_spin(placement.blocks());
+                let (field, clear) = placement.apply(&node.field);
+                let reward = node.reward + self.reward(&clear, t_spin);
+                let score = reward + Features::new(&field, 0).score(&self.weights);
+                let path = if is_root {
+                    let mut path = if held { vec![Action::Hold] } else { Vec::new() };
+                    path.extend(placement.path().iter().copied());
+                    path
+                } else {
+                    node.path.clone()
+                };
+                children.push(Node {
+                    field,
+                    hold,
+                    next,
+                    reward,
+                    score,
+                    path,
+                });
+            }
+        }
+        self.select(children)
+    }
+
+    fn select(&self, nodes: Vec<Node>) -> Vec<Node> {
+        let mut transpositions: HashMap<_, usize> = HashMap::new();
+        let mut selected: Vec<Node> = Vec::new();
+        for node in nodes {
+            let key = (node.field.clone(), node.hold, node.next);
+            match transpositions.get(&key) {
+                Some(idx) => {
+                    if node.score > selected[*idx].score {
+                        selected[*idx] = node;
+                    }
+                }
+                None => {
+                    transpositions.insert(key, selected.len());
+                    selected.push(node);
+                }
+            }
+        }
+        selected.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+        selected.truncate(self.width);
+        selected
+    }
+
+    fn reward(&self, clear: &Clear, t_spin: bool) -> f64 {
+        let lines = *clear.lines() as f64;
+        if *clear.all_clear() {
+            PERFECT_CLEAR
+        } else if t_spin {
+            T_SPIN * lines
+        } else {
+            self.weights.lines() * lines
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        ai::Ai,
+        block::{Block, Color},
+        game_mode::{marathon::Marathon, puzzle::Puzzle, GameMode},
+        tetromino_factory::TetrominoFactory,
+    };
+
+    #[test]
+    fn test_search_hold() {
+        let search = build_search(4, 2);
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        for y in 0..4 {
+            field.fix_blocks((0..9).map(|x| Block::new(Color::Grey, x, y)).collect());
+        }
+        let tetromino = TetrominoFactory::build_tetromino(TetrominoKind::S);
+        let path = search.search(
+            &field,
+            tetromino.as_ref(),
+            Some(TetrominoKind::I),
+            true,
+            &[TetrominoKind::O],
+        );
+        assert_eq!(Some(&Action::Hold), path.first());
+        assert_eq!(Some(&Action::HardDrop), path.last());
+    }
+
+    #[test]
+    fn test_search_t_spin() {
+        let search = build_search(4, 1);
+        let text = "name: A\ngoal: tsd\nqueue: T\nfield:\n....XXXXXX\nXX...XXXXX\nXXX.XXXXXX\n";
+        let field = Puzzle::parse_pack(text).unwrap()[0].initial_field();
+        let tetromino = TetrominoFactory::build_tetromino(TetrominoKind::T);
+        let path = search.search(&field, tetromino.as_ref(), None, false, &[]);
+        let placement = MoveGenerator::new(Rules::modern())
+            .generate(&field, tetromino.as_ref())
+            .into_iter()
+            .find(|placement| *placement.path() == path)
+            .unwrap();
+        assert!(placement.is_rotated());
+        assert!(field.is_t_spin(placement.blocks()));
+        assert_eq!(2, *placement.apply(&field).1.lines());
+    }
+
+    #[test]
+    fn test_budget() {
+        let search = BeamSearch::new(Rules::modern(), Weights::default(), 100, 5, -1.0, || 0.0);
+        let field = Field::new(vec![vec![None; 10]; 24]);
+        let tetromino = TetrominoFactory::build_tetromino(TetrominoKind::O);
+        let path = search.search(&field, tetromino.as_ref(), None, true, &[TetrominoKind::I]);
+        assert_eq!(Some(&Action::HardDrop), path.last());
+    }
+
+    #[test]
+    fn test_plan() {
+        let mut game = Game::new(Box::new(Marathon::new(None)));
+        let mut ai = Ai::new(Box::new(build_search(4, 2)));
+        for _ in 0..600 {
+            let input = ai.input(&mut game);
+            game.key_event(input);
+            game.update();
+        }
+        assert!(!game.phase().is_finished());
+        assert!(*game.stats().lines() > 0);
+    }
+
+    fn build_search(width: usize, depth: usize) -> BeamSearch {
+        BeamSearch::new(
+            Rules::modern(),
+            Weights::default(),
+            width,
+            depth,
+            f64::MAX,
+            || 0.0,
+        )
+    }
+}
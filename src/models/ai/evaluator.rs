@@ -0,0 +1,178 @@
+use crate::models::{field::Field, move_generator::Placement};
+
+use derive_new::new;
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 24;
+
+#[derive(Clone, Copy, Debug, new, PartialEq)]
+pub struct Weights {
+    height: f64,
+    holes: f64,
+    bumpiness: f64,
+    wells: f64,
+    row_transitions: f64,
+    column_transitions: f64,
+    lines: f64,
+}
+
+impl Weights {
+    pub fn lines(&self) -> &f64 {
+        &self.lines
+    }
+
+    pub fn to_vec(&self) -> Vec<f64> {
+        vec![
+            self.height,
+            self.holes,
+            self.bumpiness,
+            self.wells,
+            self.row_transitions,
+            self.column_transitions,
+            self.lines,
+        ]
+    }
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self::new(-0.51, -0.76, -0.18, -0.1, -0.3, -0.3, 0.76)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Features {
+    height: i32,
+    holes: i32,
+    bumpiness: i32,
+    wells: i32,
+    row_transitions: i32,
+    column_transitions: i32,
+    lines: i32,
+}
+
+impl Features {
+    pub fn new(field: &Field, lines: i32) -> Self {
+        let mut cells = [[false; WIDTH]; HEIGHT];
+        for block in field.blocks() {
+            cells[*block.y() as usize][*block.x() as usize] = true;
+        }
+        let heights: Vec<i32> = (0..WIDTH)
+            .map(|x| {
+                (0..HEIGHT)
+                    .rev()
+                    .find(|y| cells[*y][x])
+                    .map_or(0, |y| y as i32 + 1)
+            })
+            .collect();
+
+        let mut features = Self {
+            height: heights.iter().sum(),
+            lines,
+            ..Self::default()
+        };
+        for x in 0..WIDTH {
+            features.holes += (0..heights[x] as usize).filter(|y| !cells[*y][x]).count() as i32;
+            let left = if x == 0 {
+                HEIGHT as i32
+            } else {
+                heights[x - 1]
+            };
+            let right = if x == WIDTH - 1 {
+                HEIGHT as i32
+            } else {
+                heights[x + 1]
+            };
+            features.wells += (left.min(right) - heights[x]).max(0);
+            if x + 1 < WIDTH {
+                features.bumpiness += (heights[x] - heights[x + 1]).abs();
+            }
+            let mut filled = true;
+            for row in cells.iter() {
+                if row[x] != filled {
+                    features.column_transitions += 1;
+                    filled = row[x];
+                }
+            }
+        }
+        let top = *heights.iter().max().unwrap_or(&0) as usize;
+        for row in cells.iter().take(top) {
+            let mut filled = true;
+            for cell in row.iter().chain([true].iter()) {
+                if *cell != filled {
+                    features.row_transitions += 1;
+                    filled = *cell;
+                }
+            }
+        }
+        features
+    }
+
+    pub fn holes(&self) -> &i32 {
+        &self.holes
+    }
+
+    pub fn score(&self, weights: &Weights) -> f64 {
+        [
+            self.height,
+            self.holes,
+            self.bumpiness,
+            self.wells,
+            self.row_transitions,
+            self.column_transitions,
+            self.lines,
+        ]
+        .iter()
+        .zip(weights.to_vec())
+        .map(|(feature, weight)| *feature as f64 * weight)
+        .sum()
+    }
+}
+
+#[derive(Clone, Copy, Debug, new, PartialEq)]
+pub struct Evaluator {
+    weights: Weights,
+}
+
+impl Evaluator {
+    pub fn evaluate(&self, field: &Field, placement: &Placement) -> f64 {
+        let (field, clear) = placement.apply(field);
+        Features::new(&field, *clear.lines()).score(&self.weights)
+    }
+
+    pub fn weights(&self) -> &Weights {
+        &self.weights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::block::{Block, Color};
+
+    #[test]
+    fn test_features() {
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        field.fix_blocks(vec![
+            Block::new(Color::Grey, 0, 1),
+            Block::new(Color::Grey, 1, 0),
+            Block::new(Color::Grey, 3, 0),
+        ]);
+        let features = Features::new(&field, 1);
+        assert_eq!(4, features.height);
+        assert_eq!(1, *features.holes());
+        assert_eq!(4, features.bumpiness);
+        assert_eq!(1, features.wells);
+        assert_eq!(8, features.row_transitions);
+        assert_eq!(12, features.column_transitions);
+        assert_eq!(1, features.lines);
+    }
+
+    #[test]
+    fn test_score() {
+        let field = Field::new(vec![vec![None; 10]; 24]);
+        let features = Features::new(&field, 4);
+        let weights = Weights::new(0.0, 0.0, 0.0, 0.0, 0.0, -1.0, 2.0);
+        assert_eq!(8.0 - 10.0, features.score(&weights));
+    }
+}
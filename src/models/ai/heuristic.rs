@@ -0,0 +1,74 @@
+use crate::models::{
+    ai::{
+        evaluator::{Evaluator, Weights},
+        Bot,
+    },
+    field::Field,
+    game::Game,
+    input::Action,
+    move_generator::{MoveGenerator, Placement},
+    rules::Rules,
+    tetromino::Tetromino,
+};
+
+pub struct Heuristic {
+    generator: MoveGenerator,
+    evaluator: Evaluator,
+}
+
+impl Bot for Heuristic {
+    fn plan(&mut self, game: &mut Game) -> Vec<Action> {
+        match self.best(game.field(), game.tetromino()) {
+            Some(placement) => placement.path().clone(),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Heuristic {
+    pub fn new(rules: Rules, weights: Weights) -> Self {
+        Self {
+            generator: MoveGenerator::new(rules),
+            evaluator: Evaluator::new(weights),
+        }
+    }
+
+    pub fn best(&self, field: &Field, tetromino: &dyn Tetromino) -> Option<Placement> {
+        let mut best: Option<(f64, Placement)> = None;
+        for placement in self.generator.generate(field, tetromino) {
+            let score = self.evaluator.evaluate(field, &placement);
+            let is_better = match &best {
+                Some((best, _)) => score > *best,
+                None => true,
+            };
+            if is_better {
+                best = Some((score, placement));
+            }
+        }
+        best.map(|(_, placement)| placement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        block::{Block, Color},
+        tetromino::TetrominoKind,
+        tetromino_factory::TetrominoFactory,
+    };
+
+    #[test]
+    fn test_best() {
+        let heuristic = Heuristic::new(Rules::modern(), Weights::default());
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        for y in 0..4 {
+            field.fix_blocks((0..9).map(|x| Block::new(Color::Grey, x, y)).collect());
+        }
+        let tetromino = TetrominoFactory::build_tetromino(TetrominoKind::I);
+        let placement = heuristic.best(&field, tetromino.as_ref()).unwrap();
+        assert!(placement.blocks().iter().all(|block| *block.x() == 9));
+        assert!(field.is_vacant(placement.blocks()));
+        assert_eq!(Some(&Action::HardDrop), placement.path().last());
+    }
+}
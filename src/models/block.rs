@@ -1,13 +1,13 @@
 use derive_new::new;
 
-#[derive(Clone, Copy, Debug, Eq, new, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, new, PartialEq)]
 pub struct Block {
     color: Color,
     x: i32,
     y: i32,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Color {
     Cyan,
     Blue,
@@ -16,6 +16,7 @@ pub enum Color {
     Red,
     Purple,
     Yellow,
+    Grey,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -38,6 +39,14 @@ impl Block {
         Self { color, x, y }
     }
 
+    pub fn shift(&self, x: i32, y: i32) -> Self {
+        Self {
+            color: self.color,
+            x: self.x + x,
+            y: self.y + y,
+        }
+    }
+
     pub fn color(&self) -> &Color {
         &self.color
     }
@@ -54,6 +63,23 @@ impl Block {
         &self,
         context: &web_sys::CanvasRenderingContext2d,
         image: &web_sys::HtmlImageElement,
+    ) {
+        self.draw_scaled(
+            context,
+            image,
+            self.x_idx_on_canvas(),
+            self.y_idx_on_canvas(),
+            32.0,
+        );
+    }
+
+    pub fn draw_scaled(
+        &self,
+        context: &web_sys::CanvasRenderingContext2d,
+        image: &web_sys::HtmlImageElement,
+        x: f64,
+        y: f64,
+        size: f64,
     ) {
         context
             .draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
@@ -62,13 +88,23 @@ impl Block {
                 0.0,
                 32.0,
                 32.0,
-                self.x_idx_on_canvas(),
-                self.y_idx_on_canvas(),
-                32.0,
-                32.0,
+                x,
+                y,
+                size,
+                size,
             )
             .expect(format!("Failed to draw image {:?}", image).as_str());
     }
+
+    pub fn draw_outline(&self, context: &web_sys::CanvasRenderingContext2d) {
+        context.set_line_width(2.0);
+        context.stroke_rect(
+            self.x_idx_on_canvas() + 1.0,
+            self.y_idx_on_canvas() + 1.0,
+            30.0,
+            30.0,
+        );
+    }
 }
 
 impl Block {
@@ -81,6 +117,7 @@ impl Block {
             Color::Red => 5.0,
             Color::Purple => 6.0,
             Color::Yellow => 7.0,
+            Color::Grey => 8.0,
         }
     }
 
@@ -0,0 +1,32 @@
+use derive_new::new;
+
+#[derive(Clone, Copy, Debug, Eq, new, PartialEq)]
+pub struct Clear {
+    lines: i32,
+    all_clear: bool,
+}
+
+impl Clear {
+    pub fn lines(&self) -> &i32 {
+        &self.lines
+    }
+
+    pub fn all_clear(&self) -> &bool {
+        &self.all_clear
+    }
+
+    pub fn scale_down(&self, scale: i32) -> Self {
+        Self::new((self.lines + scale - 1) / scale, self.all_clear)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_down() {
+        assert_eq!(Clear::new(2, false), Clear::new(4, false).scale_down(2));
+        assert_eq!(Clear::new(1, true), Clear::new(1, true).scale_down(2));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::models::clear::Clear;
+
+use derive_new::new;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Combo {
+    combo: i32,
+    back_to_back: i32,
+}
+
+#[derive(Clone, Copy, Debug, Eq, new, PartialEq)]
+pub struct ClearEvent {
+    lines: i32,
+    t_spin: bool,
+    combo: i32,
+    back_to_back: i32,
+    all_clear: bool,
+}
+
+impl Combo {
+    pub fn new() -> Self {
+        Self {
+            combo: -1,
+            back_to_back: -1,
+        }
+    }
+
+    pub fn update(&mut self, clear: Clear, t_spin: bool) -> ClearEvent {
+        let lines = *clear.lines();
+        if lines == 0 {
+            self.combo = -1;
+        } else {
+            self.combo += 1;
+            self.back_to_back = if lines == 4 || t_spin {
+                self.back_to_back + 1
+            } else {
+                -1
+            };
+        }
+        ClearEvent::new(
+            lines,
+            t_spin,
+            self.combo,
+            self.back_to_back,
+            *clear.all_clear(),
+        )
+    }
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClearEvent {
+    pub fn lines(&self) -> &i32 {
+        &self.lines
+    }
+
+    pub fn t_spin(&self) -> &bool {
+        &self.t_spin
+    }
+
+    pub fn combo(&self) -> &i32 {
+        &self.combo
+    }
+
+    pub fn back_to_back(&self) -> &i32 {
+        &self.back_to_back
+    }
+
+    pub fn all_clear(&self) -> &bool {
+        &self.all_clear
+    }
+
+    pub fn is_difficult(&self) -> bool {
+        self.lines == 4 || (self.t_spin && self.lines > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_combo() {
+        let mut combo = Combo::new();
+        assert_eq!(0, *combo.update(Clear::new(1, false), false).combo());
+        assert_eq!(1, *combo.update(Clear::new(2, false), false).combo());
+        assert_eq!(-1, *combo.update(Clear::new(0, false), false).combo());
+        assert_eq!(0, *combo.update(Clear::new(1, false), false).combo());
+    }
+
+    #[test]
+    fn test_update_back_to_back() {
+        let mut combo = Combo::new();
+        assert_eq!(0, *combo.update(Clear::new(4, false), false).back_to_back());
+        assert_eq!(0, *combo.update(Clear::new(0, false), false).back_to_back());
+        assert_eq!(1, *combo.update(Clear::new(2, false), true).back_to_back());
+        assert_eq!(1, *combo.update(Clear::new(0, false), true).back_to_back());
+        assert_eq!(
+            -1,
+            *combo.update(Clear::new(1, false), false).back_to_back()
+        );
+    }
+
+    #[test]
+    fn test_is_difficult() {
+        let mut combo = Combo::new();
+        assert!(combo.update(Clear::new(4, false), false).is_difficult());
+        assert!(combo.update(Clear::new(1, false), true).is_difficult());
+        assert!(!combo.update(Clear::new(0, false), true).is_difficult());
+        assert!(!combo.update(Clear::new(3, true), false).is_difficult());
+        assert!(*combo.update(Clear::new(3, true), false).all_clear());
+    }
+}
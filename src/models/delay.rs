@@ -0,0 +1,22 @@
+use derive_new::new;
+
+#[derive(Clone, Copy, Debug, Eq, new, PartialEq)]
+pub struct Delay {
+    are: i32,
+    line_clear: i32,
+    lock: i32,
+}
+
+impl Delay {
+    pub fn are(&self) -> &i32 {
+        &self.are
+    }
+
+    pub fn line_clear(&self) -> &i32 {
+        &self.line_clear
+    }
+
+    pub fn lock(&self) -> &i32 {
+        &self.lock
+    }
+}
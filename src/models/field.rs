@@ -1,11 +1,18 @@
-use crate::models::block::{Block, Color};
+use crate::models::{
+    block::{Block, Color},
+    clear::Clear,
+};
 
-use derive_new::new;
+use std::hash::{Hash, Hasher};
 
-#[derive(Clone, Debug, Eq, PartialEq, new)]
-pub struct Field(Vec<Vec<Option<Color>>>);
+#[derive(Clone, Debug, Eq)]
+pub struct Field(Vec<Vec<Option<Color>>>, Vec<Vec<i32>>);
 
 impl Field {
+    pub fn new(rows: Vec<Vec<Option<Color>>>) -> Self {
+        let locked_at = rows.iter().map(|row| vec![0; row.len()]).collect();
+        Self(rows, locked_at)
+    }
     pub fn is_vacant(&self, blocks: &Vec<Block>) -> bool {
         blocks.iter().all(|block| match self.get(block) {
             Some(color_or_none) => color_or_none.is_none(),
@@ -16,19 +23,69 @@ impl Field {
         blocks.iter().any(|block| *block.y() < 20)
     }
     pub fn fix_blocks(&mut self, blocks: Vec<Block>) {
+        self.fix_blocks_at(blocks, 0);
+    }
+    pub fn fix_blocks_at(&mut self, blocks: Vec<Block>, frame: i32) {
         for block in blocks.iter() {
-            self.set(block);
+            self.set(block, frame);
         }
     }
-    pub fn clear_blocks(&mut self) -> i32 {
-        (0..24).rev().fold(0, |score, row_idx| {
+    pub fn clear_blocks(&mut self) -> Clear {
+        let lines = (0..24).rev().fold(0, |score, row_idx| {
             if self.is_filled(row_idx) {
                 self.clear(row_idx);
                 score + 1
             } else {
                 score
             }
-        })
+        });
+        Clear::new(lines, lines > 0 && self.blocks().is_empty())
+    }
+    pub fn is_t_spin(&self, blocks: &[Block]) -> bool {
+        let center = blocks.iter().find(|block| {
+            blocks
+                .iter()
+                .filter(|other| (block.x() - other.x()).abs() + (block.y() - other.y()).abs() == 1)
+                .count()
+                == 3
+        });
+        match center {
+            Some(center) => {
+                [(-1, -1), (-1, 1), (1, -1), (1, 1)]
+                    .iter()
+                    .filter(|(x, y)| match self.get(&center.shift(*x, *y)) {
+                        Some(color_or_none) => color_or_none.is_some(),
+                        None => true,
+                    })
+                    .count()
+                    >= 3
+            }
+            None => false,
+        }
+    }
+    pub fn insert_garbage(&mut self, holes: &[i32]) -> bool {
+        let mut topped_out = false;
+        for hole in holes.iter() {
+            let mut row = vec![Some(Color::Grey); 10];
+            if let Some(color_or_none) = row.get_mut(*hole as usize) {
+                *color_or_none = None;
+            }
+            self.0.insert(0, row);
+            self.1.insert(0, vec![0; 10]);
+            self.1.pop();
+            let removed = self.0.pop().unwrap_or_default();
+            topped_out |= removed.iter().any(|color_or_none| color_or_none.is_some());
+        }
+        !topped_out
+    }
+    pub fn contains(&self, color: Color) -> bool {
+        self.0
+            .iter()
+            .flatten()
+            .any(|color_or_none| *color_or_none == Some(color))
+    }
+    pub fn filled_rows(&self) -> Vec<i32> {
+        (0..24).filter(|row_idx| self.is_filled(*row_idx)).collect()
     }
     pub fn blocks(&self) -> Vec<Block> {
         let mut blocks = Vec::new();
@@ -41,6 +98,12 @@ impl Field {
         }
         blocks
     }
+    pub fn locked_at(&self, block: &Block) -> Option<&i32> {
+        match self.1.get(*block.y() as usize) {
+            None => None,
+            Some(row) => row.get(*block.x() as usize),
+        }
+    }
 }
 
 impl Field {
@@ -50,12 +113,17 @@ impl Field {
             Some(row) => row.get(*block.x() as usize),
         }
     }
-    fn set(&mut self, block: &Block) {
+    fn set(&mut self, block: &Block, frame: i32) {
         if let Some(row) = self.0.get_mut(*block.y() as usize) {
             if let Some(color_or_none) = row.get_mut(*block.x() as usize) {
                 *color_or_none = Some(*block.color());
             }
         }
+        if let Some(row) = self.1.get_mut(*block.y() as usize) {
+            if let Some(locked_at) = row.get_mut(*block.x() as usize) {
+                *locked_at = frame;
+            }
+        }
     }
     fn is_filled(&self, row_idx: i32) -> bool {
         match self.0.get(row_idx as usize) {
@@ -66,12 +134,28 @@ impl Field {
     fn clear(&mut self, row_idx: i32) {
         self.0.remove(row_idx as usize);
         self.0.push(vec![None; 10]);
+        self.1.remove(row_idx as usize);
+        self.1.push(vec![0; 10]);
+    }
+}
+
+// Lock frames only drive the fading display, so two fields with the same cells are equal.
+impl PartialEq for Field {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Hash for Field {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     #[test]
     fn test_is_vacant_1() {
@@ -136,7 +220,7 @@ mod tests {
     fn test_clear_blocks_1() {
         let mut field = build_field_with_missing_lines(4);
         let field_clone = field.clone();
-        assert_eq!(0, field.clear_blocks());
+        assert_eq!(Clear::new(0, false), field.clear_blocks());
         assert_eq!(field_clone, field);
         assert_eq!(24, field.0.len());
     }
@@ -145,7 +229,7 @@ mod tests {
     fn test_clear_blocks_2() {
         let mut field = build_field_with_missing_lines(5);
         field.0[3][0] = Some(Color::Cyan);
-        assert_eq!(1, field.clear_blocks());
+        assert_eq!(Clear::new(1, false), field.clear_blocks());
         assert_eq!(missing_line(), field.0[3]);
         assert_eq!(missing_line(), field.0[2]);
         assert_eq!(missing_line(), field.0[1]);
@@ -158,7 +242,7 @@ mod tests {
         let mut field = build_field_with_missing_lines(5);
         field.0[3][0] = Some(Color::Cyan);
         field.0[2][0] = Some(Color::Cyan);
-        assert_eq!(2, field.clear_blocks());
+        assert_eq!(Clear::new(2, false), field.clear_blocks());
         assert_eq!(vec![None; 10], field.0[3]);
         assert_eq!(missing_line(), field.0[2]);
         assert_eq!(missing_line(), field.0[1]);
@@ -172,7 +256,7 @@ mod tests {
         field.0[3][0] = Some(Color::Cyan);
         field.0[2][0] = Some(Color::Cyan);
         field.0[1][0] = Some(Color::Cyan);
-        assert_eq!(3, field.clear_blocks());
+        assert_eq!(Clear::new(3, false), field.clear_blocks());
         assert_eq!(vec![None; 10], field.0[3]);
         assert_eq!(vec![None; 10], field.0[2]);
         assert_eq!(missing_line(), field.0[1]);
@@ -187,7 +271,7 @@ mod tests {
         field.0[2][0] = Some(Color::Cyan);
         field.0[1][0] = Some(Color::Cyan);
         field.0[0][0] = Some(Color::Cyan);
-        assert_eq!(4, field.clear_blocks());
+        assert_eq!(Clear::new(4, false), field.clear_blocks());
         assert_eq!(vec![None; 10], field.0[3]);
         assert_eq!(vec![None; 10], field.0[2]);
         assert_eq!(vec![None; 10], field.0[1]);
@@ -195,6 +279,99 @@ mod tests {
         assert_eq!(24, field.0.len());
     }
 
+    #[test]
+    fn test_clear_blocks_6() {
+        let mut field = build_field_with_missing_lines(2);
+        field.0[1][0] = Some(Color::Cyan);
+        field.0[0][0] = Some(Color::Cyan);
+        assert_eq!(Clear::new(2, true), field.clear_blocks());
+        assert_eq!(Field::new(vec![vec![None; 10]; 24]), field);
+    }
+
+    #[test]
+    fn test_is_t_spin_1() {
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        field.0[0][0] = Some(Color::Cyan);
+        field.0[0][2] = Some(Color::Cyan);
+        field.0[2][0] = Some(Color::Cyan);
+        let blocks = vec![
+            Block::new(Color::Purple, 0, 1),
+            Block::new(Color::Purple, 1, 0),
+            Block::new(Color::Purple, 1, 1),
+            Block::new(Color::Purple, 2, 1),
+        ];
+        assert!(field.is_t_spin(&blocks));
+        field.0[2][0] = None;
+        assert!(!field.is_t_spin(&blocks));
+    }
+
+    #[test]
+    fn test_is_t_spin_2() {
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        let blocks = vec![
+            Block::new(Color::Purple, 0, 0),
+            Block::new(Color::Purple, 0, 1),
+            Block::new(Color::Purple, 1, 0),
+            Block::new(Color::Purple, 1, 1),
+        ];
+        assert!(!field.is_t_spin(&blocks));
+        let blocks = vec![
+            Block::new(Color::Purple, 0, 0),
+            Block::new(Color::Purple, 0, 1),
+            Block::new(Color::Purple, 0, 2),
+            Block::new(Color::Purple, 1, 1),
+        ];
+        assert!(!field.is_t_spin(&blocks));
+        field.0[0][1] = Some(Color::Cyan);
+        assert!(field.is_t_spin(&blocks));
+    }
+
+    #[test]
+    fn test_insert_garbage_1() {
+        let mut field = build_field_with_blocks(4);
+        assert!(field.insert_garbage(&[2, 5]));
+        let mut garbage = vec![Some(Color::Grey); 10];
+        garbage[5] = None;
+        assert_eq!(garbage, field.0[0]);
+        garbage[5] = Some(Color::Grey);
+        garbage[2] = None;
+        assert_eq!(garbage, field.0[1]);
+        assert_eq!(Some(Color::Cyan), field.0[5][0]);
+        assert_eq!(None, field.0[6][0]);
+        assert_eq!(24, field.0.len());
+    }
+
+    #[test]
+    fn test_insert_garbage_2() {
+        let mut field = build_field_with_blocks(23);
+        assert!(field.insert_garbage(&[0]));
+        assert!(!field.insert_garbage(&[0]));
+        assert_eq!(24, field.0.len());
+    }
+
+    #[test]
+    fn test_contains() {
+        let mut field = build_field_with_blocks(4);
+        assert!(field.contains(Color::Cyan));
+        assert!(!field.contains(Color::Grey));
+        field.insert_garbage(&[0]);
+        assert!(field.contains(Color::Grey));
+    }
+
+    #[test]
+    fn test_filled_rows_1() {
+        let field = build_field_with_missing_lines(4);
+        assert_eq!(Vec::<i32>::new(), field.filled_rows());
+    }
+
+    #[test]
+    fn test_filled_rows_2() {
+        let mut field = build_field_with_missing_lines(5);
+        field.0[3][0] = Some(Color::Cyan);
+        field.0[1][0] = Some(Color::Cyan);
+        assert_eq!(vec![1, 3], field.filled_rows());
+    }
+
     #[test]
     fn test_blocks_1() {
         let field = build_field_with_blocks(4);
@@ -203,16 +380,42 @@ mod tests {
 
     #[test]
     fn test_blocks_2() {
-        let field = Field(vec![vec![None; 10]; 24]);
+        let field = Field::new(vec![vec![None; 10]; 24]);
         assert_eq!(Vec::<Block>::new(), field.blocks());
     }
 
+    #[test]
+    fn test_locked_at() {
+        let mut field = build_field_with_missing_lines(2);
+        field.fix_blocks_at(vec![Block::new(Color::Cyan, 3, 5)], 42);
+        field.fix_blocks_at(vec![Block::new(Color::Cyan, 0, 0)], 10);
+        assert_eq!(Some(&42), field.locked_at(&Block::new(Color::Cyan, 3, 5)));
+        assert_eq!(Clear::new(1, false), field.clear_blocks());
+        assert_eq!(Some(&42), field.locked_at(&Block::new(Color::Cyan, 3, 4)));
+        assert_eq!(Some(&0), field.locked_at(&Block::new(Color::Cyan, 3, 5)));
+        assert_eq!(None, field.locked_at(&Block::new(Color::Cyan, 10, 0)));
+    }
+
+    #[test]
+    fn test_eq() {
+        let mut field = build_field_with_missing_lines(2);
+        let mut other = field.clone();
+        field.fix_blocks_at(vec![Block::new(Color::Cyan, 3, 5)], 42);
+        other.fix_blocks_at(vec![Block::new(Color::Cyan, 3, 5)], 10);
+        assert_eq!(field, other);
+        let mut set = HashSet::new();
+        set.insert(field);
+        assert!(set.contains(&other));
+        other.fix_blocks(vec![Block::new(Color::Cyan, 4, 5)]);
+        assert!(!set.contains(&other));
+    }
+
     fn build_field_with_blocks(num: i32) -> Field {
         let mut field = vec![vec![None; 10]; 24];
         for row_idx in 0..num {
             field[row_idx as usize][0] = Some(Color::Cyan);
         }
-        Field(field)
+        Field::new(field)
     }
 
     fn build_field_with_missing_lines(num: i32) -> Field {
@@ -220,7 +423,7 @@ mod tests {
         for row_idx in 0..num {
             field[row_idx as usize] = missing_line();
         }
-        Field(field)
+        Field::new(field)
     }
 
     fn missing_line() -> Vec<Option<Color>> {
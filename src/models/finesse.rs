@@ -0,0 +1,200 @@
+use crate::models::{
+    field::Field,
+    rules::Rules,
+    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection, TetrominoKind},
+};
+
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+
+const KINDS: [TetrominoKind; 7] = [
+    TetrominoKind::I,
+    TetrominoKind::J,
+    TetrominoKind::L,
+    TetrominoKind::O,
+    TetrominoKind::S,
+    TetrominoKind::T,
+    TetrominoKind::Z,
+];
+const KEYS: [Key; 7] = [
+    Key::TapLeft,
+    Key::TapRight,
+    Key::DasLeft,
+    Key::DasRight,
+    Key::RotateLeft,
+    Key::RotateRight,
+    Key::RotateHalf,
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Key {
+    TapLeft,
+    TapRight,
+    DasLeft,
+    DasRight,
+    RotateLeft,
+    RotateRight,
+    RotateHalf,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Finesse {
+    table: HashMap<(TetrominoKind, TetrominoDirection, i32), i32>,
+}
+
+impl Finesse {
+    pub fn new(rules: &Rules) -> Self {
+        let mut table = HashMap::new();
+        for kind in KINDS.iter() {
+            table.extend(Self::search(rules, *kind));
+        }
+        Self { table }
+    }
+
+    pub fn optimal(
+        &self,
+        kind: TetrominoKind,
+        dir: TetrominoDirection,
+        column: i32,
+    ) -> Option<&i32> {
+        self.table.get(&(kind, dir, column))
+    }
+
+    pub fn is_fault(&self, tetromino: &dyn Tetromino, presses: i32) -> bool {
+        let column = Self::column(tetromino);
+        match self.optimal(tetromino.kind(), tetromino.direction(), column) {
+            Some(optimal) => presses > *optimal,
+            None => false,
+        }
+    }
+}
+
+impl Finesse {
+    // Searches the fewest key presses from spawn on an empty field; placements covering the same
+    // cells share the cheapest cost, so an S rotated twice is judged against the unrotated one.
+    fn search(
+        rules: &Rules,
+        kind: TetrominoKind,
+    ) -> Vec<((TetrominoKind, TetrominoDirection, i32), i32)> {
+        let field = Field::new(vec![vec![None; 10]; 24]);
+        let tetromino = rules.build_tetromino(kind);
+        let mut costs = HashMap::new();
+        let mut queue = VecDeque::new();
+        costs.insert(
+            Self::state(tetromino.as_ref()),
+            (0, Self::footprint(tetromino.as_ref())),
+        );
+        queue.push_back((tetromino, 0));
+
+        while let Some((tetromino, cost)) = queue.pop_front() {
+            for key in KEYS.iter() {
+                let mut next = tetromino.clone();
+                if !Self::press(next.as_mut(), *key, &field, *rules.kicks()) {
+                    continue;
+                }
+                if let Entry::Vacant(entry) = costs.entry(Self::state(next.as_ref())) {
+                    entry.insert((cost + 1, Self::footprint(next.as_ref())));
+                    queue.push_back((next, cost + 1));
+                }
+            }
+        }
+
+        let mut cheapest: HashMap<Vec<(i32, i32)>, i32> = HashMap::new();
+        for (cost, footprint) in costs.values() {
+            let entry = cheapest.entry(footprint.clone()).or_insert(*cost);
+            *entry = (*entry).min(*cost);
+        }
+        costs
+            .into_iter()
+            .map(|((dir, column), (_, footprint))| ((kind, dir, column), cheapest[&footprint]))
+            .collect()
+    }
+
+    fn press(tetromino: &mut dyn Tetromino, key: Key, field: &Field, kicks: bool) -> bool {
+        match key {
+            Key::TapLeft => tetromino.try_move(MoveDirection::Left, field),
+            Key::TapRight => tetromino.try_move(MoveDirection::Right, field),
+            Key::DasLeft | Key::DasRight => {
+                let move_dir = if key == Key::DasLeft {
+                    MoveDirection::Left
+                } else {
+                    MoveDirection::Right
+                };
+                let mut moved = false;
+                while tetromino.try_move(move_dir, field) {
+                    moved = true;
+                }
+                moved
+            }
+            Key::RotateLeft => tetromino.try_rotate(RotateDirection::Left, field, kicks),
+            Key::RotateRight => tetromino.try_rotate(RotateDirection::Right, field, kicks),
+            Key::RotateHalf => tetromino.try_rotate(RotateDirection::Half, field, kicks),
+        }
+    }
+
+    fn state(tetromino: &dyn Tetromino) -> (TetrominoDirection, i32) {
+        (tetromino.direction(), Self::column(tetromino))
+    }
+
+    fn column(tetromino: &dyn Tetromino) -> i32 {
+        tetromino
+            .blocks()
+            .iter()
+            .map(|block| *block.x())
+            .min()
+            .unwrap()
+    }
+
+    fn footprint(tetromino: &dyn Tetromino) -> Vec<(i32, i32)> {
+        let blocks = tetromino.blocks();
+        let bottom = blocks.iter().map(|block| *block.y()).min().unwrap();
+        let mut footprint: Vec<_> = blocks
+            .iter()
+            .map(|block| (*block.x(), *block.y() - bottom))
+            .collect();
+        footprint.sort();
+        footprint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimal() {
+        let finesse = Finesse::new(&Rules::modern());
+        let spawn = TetrominoDirection::Right;
+        assert_eq!(Some(&0), finesse.optimal(TetrominoKind::T, spawn, 3));
+        assert_eq!(Some(&1), finesse.optimal(TetrominoKind::T, spawn, 0));
+        assert_eq!(Some(&1), finesse.optimal(TetrominoKind::T, spawn, 7));
+        assert_eq!(Some(&2), finesse.optimal(TetrominoKind::T, spawn, 1));
+        assert_eq!(
+            Some(&1),
+            finesse.optimal(TetrominoKind::T, TetrominoDirection::Left, 3)
+        );
+        assert_eq!(Some(&1), finesse.optimal(TetrominoKind::O, spawn, 0));
+        assert_eq!(None, finesse.optimal(TetrominoKind::O, spawn, 9));
+    }
+
+    #[test]
+    fn test_optimal_equivalent() {
+        let finesse = Finesse::new(&Rules::modern());
+        for column in 0..8 {
+            assert_eq!(
+                finesse.optimal(TetrominoKind::S, TetrominoDirection::Right, column),
+                finesse.optimal(TetrominoKind::S, TetrominoDirection::Left, column)
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_fault() {
+        let rules = Rules::modern();
+        let finesse = Finesse::new(&rules);
+        let field = Field::new(vec![vec![None; 10]; 24]);
+        let mut tetromino = rules.build_tetromino(TetrominoKind::T);
+        while tetromino.try_move(MoveDirection::Left, &field) {}
+        assert!(!finesse.is_fault(tetromino.as_ref(), 1));
+        assert!(finesse.is_fault(tetromino.as_ref(), 3));
+    }
+}
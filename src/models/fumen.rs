@@ -0,0 +1,649 @@
+use crate::models::{
+    block::{Block, Color},
+    field::Field,
+    move_generator::Placement,
+    tetromino::{TetrominoDirection, TetrominoKind},
+};
+
+use derive_new::new;
+
+const TABLE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const COMMENT_TABLE: &str =
+    " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+const PREFIX: &str = "v115@";
+const WIDTH: i32 = 10;
+const TOP: i32 = 23;
+// The playfield rows plus the garbage row below the floor.
+const CELLS: usize = ((TOP + 1) * WIDTH) as usize;
+const UNCHANGED: i32 = 8 * CELLS as i32 + CELLS as i32 - 1;
+
+#[derive(Clone, Debug, Eq, new, PartialEq)]
+pub struct Page {
+    field: Field,
+    piece: Option<Placement>,
+    comment: String,
+}
+
+impl Page {
+    pub fn field(&self) -> &Field {
+        &self.field
+    }
+
+    pub fn piece(&self) -> &Option<Placement> {
+        &self.piece
+    }
+
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+}
+
+#[derive(Clone, Debug, Eq, new, PartialEq)]
+pub struct Fumen(Vec<Page>);
+
+impl Fumen {
+    // Accepts a bare `v115@...` string as well as a viewer URL that embeds one.
+    pub fn decode(text: &str) -> Result<Self, String> {
+        let data = match text.find(PREFIX) {
+            Some(idx) => &text[idx + PREFIX.len()..],
+            None => return Err("not a v115 fumen".to_string()),
+        };
+        let mut values = Values::decode(data)?;
+        let mut pages = Vec::new();
+        let mut cells = vec![0; CELLS];
+        let mut comment = String::new();
+        let mut repeat = 0;
+        while !values.is_empty() {
+            if repeat > 0 {
+                repeat -= 1;
+            } else if !Self::decode_field(&mut values, &mut cells)? {
+                repeat = values.poll(1)?;
+            }
+
+            let mut action = values.poll(3)?;
+            let kind = action % 8;
+            action /= 8;
+            let rotation = action % 4;
+            action /= 4;
+            let coordinate = action % CELLS as i32;
+            action /= CELLS as i32;
+            let flags: Vec<bool> = (0..5).map(|idx| action >> idx & 1 == 1).collect();
+            let (rise, mirror, has_comment, lock) = (flags[0], flags[1], flags[3], !flags[4]);
+
+            if has_comment {
+                let length = values.poll(2)? as usize;
+                let mut escaped = String::new();
+                for _ in 0..length.div_ceil(4) {
+                    let mut value = values.poll(5)?;
+                    for _ in 0..4 {
+                        let idx = (value % 96) as usize;
+                        escaped.push(
+                            COMMENT_TABLE.as_bytes().get(idx).copied().unwrap_or(b' ') as char
+                        );
+                        value /= 96;
+                    }
+                }
+                escaped.truncate(length);
+                comment = Self::unescape(&escaped);
+            }
+
+            let piece = Self::decode_piece(kind, rotation, coordinate);
+            pages.push(Page::new(
+                Self::to_field(&cells),
+                piece.clone(),
+                comment.clone(),
+            ));
+
+            if lock {
+                if let Some(piece) = piece.as_ref() {
+                    for block in piece.blocks() {
+                        cells[Self::index(*block.x(), *block.y())] = Self::code(*block.color());
+                    }
+                }
+                Self::clear_lines(&mut cells);
+                if rise {
+                    cells.rotate_left(WIDTH as usize);
+                    for cell in cells[CELLS - WIDTH as usize..].iter_mut() {
+                        *cell = 0;
+                    }
+                }
+                if mirror {
+                    for row in cells.chunks_mut(WIDTH as usize).take(TOP as usize) {
+                        row.reverse();
+                    }
+                }
+            }
+        }
+        Ok(Self(pages))
+    }
+
+    pub fn encode(&self) -> String {
+        let mut values = Values(Vec::new());
+        let mut cells = vec![0; CELLS];
+        let mut comment = "";
+        let mut repeat: Option<usize> = None;
+        for (idx, page) in self.0.iter().enumerate() {
+            let current = Self::from_field(&page.field);
+            let (field, changed) = Self::encode_field(&cells, &current);
+            match repeat {
+                Some(at) if !changed && values.0[at] < 63 => values.0[at] += 1,
+                _ => {
+                    values.0.extend(field);
+                    repeat = if changed {
+                        None
+                    } else {
+                        values.0.push(0);
+                        Some(values.0.len() - 1)
+                    };
+                }
+            }
+            cells = current;
+
+            let piece = page.piece.as_ref().and_then(Self::encode_piece);
+            let (kind, rotation, coordinate) = piece.unwrap_or((0, 0, 0));
+            let has_comment = page.comment != comment;
+            let flags = (idx == 0) as i32 * 4 + has_comment as i32 * 8;
+            values.push(
+                kind + 8 * (rotation + 4 * (coordinate + CELLS as i32 * flags)),
+                3,
+            );
+
+            if has_comment {
+                let mut escaped = Self::escape(&page.comment);
+                escaped.truncate(4095);
+                values.push(escaped.len() as i32, 2);
+                for chunk in escaped.as_bytes().chunks(4) {
+                    let value = chunk.iter().rev().fold(0, |value, byte| {
+                        value * 96 + COMMENT_TABLE.find(*byte as char).unwrap_or(0) as i32
+                    });
+                    values.push(value, 5);
+                }
+                comment = &page.comment;
+            }
+
+            if let (Some(placement), Some(_)) = (page.piece.as_ref(), piece) {
+                for block in placement.blocks() {
+                    cells[Self::index(*block.x(), *block.y())] = Self::code(*block.color());
+                }
+            }
+            Self::clear_lines(&mut cells);
+        }
+        format!("{}{}", PREFIX, values.encode())
+    }
+
+    pub fn pages(&self) -> &Vec<Page> {
+        &self.0
+    }
+}
+
+impl Fumen {
+    // Applies one page of run-length encoded differences and reports whether anything changed.
+    fn decode_field(values: &mut Values, cells: &mut [i32]) -> Result<bool, String> {
+        let mut changed = true;
+        let mut idx = 0;
+        while idx < CELLS {
+            let value = values.poll(2)?;
+            if value == UNCHANGED {
+                changed = false;
+            }
+            let diff = value / CELLS as i32 - 8;
+            let count = (value % CELLS as i32) as usize + 1;
+            if idx + count > CELLS {
+                return Err("field data overflows the field".to_string());
+            }
+            for cell in cells[idx..idx + count].iter_mut() {
+                *cell += diff;
+                if !(0..=8).contains(cell) {
+                    return Err("invalid block in field data".to_string());
+                }
+            }
+            idx += count;
+        }
+        Ok(changed)
+    }
+
+    fn encode_field(prev: &[i32], current: &[i32]) -> (Vec<i32>, bool) {
+        let diffs: Vec<i32> = current
+            .iter()
+            .zip(prev.iter())
+            .map(|(current, prev)| current - prev + 8)
+            .collect();
+        let mut values = Values(Vec::new());
+        let mut start = 0;
+        for idx in 1..=CELLS {
+            if idx == CELLS || diffs[idx] != diffs[start] {
+                values.push(diffs[start] * CELLS as i32 + (idx - start - 1) as i32, 2);
+                start = idx;
+            }
+        }
+        let changed = diffs.iter().any(|diff| *diff != 8);
+        (values.0, changed)
+    }
+
+    fn decode_piece(kind: i32, rotation: i32, coordinate: i32) -> Option<Placement> {
+        let kind = match kind {
+            1 => TetrominoKind::I,
+            2 => TetrominoKind::L,
+            3 => TetrominoKind::O,
+            4 => TetrominoKind::Z,
+            5 => TetrominoKind::T,
+            6 => TetrominoKind::J,
+            7 => TetrominoKind::S,
+            _ => return None,
+        };
+        let (dx, dy) = Self::adjustment(kind, rotation);
+        let x = coordinate % WIDTH + dx;
+        let y = TOP - 1 - coordinate / WIDTH + dy;
+        let color = Self::color(Self::kind_code(kind)).unwrap();
+        let mut blocks: Vec<Block> = Self::shape(kind, rotation)
+            .iter()
+            .map(|(cx, cy)| Block::new(color, x + cx, y + cy))
+            .collect();
+        if blocks
+            .iter()
+            .any(|block| !(0..WIDTH).contains(block.x()) || !(0..TOP).contains(block.y()))
+        {
+            return None;
+        }
+        blocks.sort_by_key(|block| (*block.y(), *block.x()));
+        Some(Placement::new(
+            kind,
+            Self::direction(rotation),
+            blocks,
+            Vec::new(),
+        ))
+    }
+
+    // Finds the rotation and rotation centre that cover the placement's cells.
+    fn encode_piece(placement: &Placement) -> Option<(i32, i32, i32)> {
+        let kind = *placement.kind();
+        let preferred = (0..4).find(|rotation| Self::direction(*rotation) == *placement.dir())?;
+        let mut cells: Vec<_> = placement
+            .blocks()
+            .iter()
+            .map(|block| (*block.x(), *block.y()))
+            .collect();
+        cells.sort();
+        for rotation in [preferred, 0, 1, 2, 3].iter() {
+            for (x, y) in cells.iter() {
+                let mut covered: Vec<_> = Self::shape(kind, *rotation)
+                    .iter()
+                    .map(|(cx, cy)| (x + cx, y + cy))
+                    .collect();
+                covered.sort();
+                if covered != cells {
+                    continue;
+                }
+                let (dx, dy) = Self::adjustment(kind, *rotation);
+                let (x, y) = (x - dx, y - dy);
+                if !(0..WIDTH).contains(&x) || !(0..TOP).contains(&y) {
+                    return None;
+                }
+                return Some((Self::kind_code(kind), *rotation, (TOP - 1 - y) * WIDTH + x));
+            }
+        }
+        None
+    }
+
+    // Rotations are numbered 180, clockwise, spawn and counterclockwise.
+    fn shape(kind: TetrominoKind, rotation: i32) -> Vec<(i32, i32)> {
+        let spawn = match kind {
+            TetrominoKind::I => [(0, 0), (-1, 0), (1, 0), (2, 0)],
+            TetrominoKind::J => [(0, 0), (-1, 0), (1, 0), (-1, 1)],
+            TetrominoKind::L => [(0, 0), (-1, 0), (1, 0), (1, 1)],
+            TetrominoKind::O => [(0, 0), (1, 0), (0, 1), (1, 1)],
+            TetrominoKind::S => [(0, 0), (-1, 0), (0, 1), (1, 1)],
+            TetrominoKind::T => [(0, 0), (-1, 0), (1, 0), (0, 1)],
+            TetrominoKind::Z => [(0, 0), (1, 0), (0, 1), (-1, 1)],
+        };
+        spawn
+            .iter()
+            .map(|(x, y)| match rotation {
+                0 => (-x, -y),
+                1 => (*y, -x),
+                3 => (-y, *x),
+                _ => (*x, *y),
+            })
+            .collect()
+    }
+
+    // Fumen anchors some pieces on a different cell than their rotation centre.
+    fn adjustment(kind: TetrominoKind, rotation: i32) -> (i32, i32) {
+        match (kind, rotation) {
+            (TetrominoKind::O, 3) => (1, -1),
+            (TetrominoKind::O, 0) | (TetrominoKind::I, 0) | (TetrominoKind::Z, 3) => (1, 0),
+            (TetrominoKind::O, 2)
+            | (TetrominoKind::I, 3)
+            | (TetrominoKind::S, 2)
+            | (TetrominoKind::Z, 2) => (0, -1),
+            (TetrominoKind::S, 1) => (-1, 0),
+            _ => (0, 0),
+        }
+    }
+
+    fn direction(rotation: i32) -> TetrominoDirection {
+        match rotation {
+            0 => TetrominoDirection::Left,
+            1 => TetrominoDirection::Down,
+            3 => TetrominoDirection::Up,
+            _ => TetrominoDirection::Right,
+        }
+    }
+
+    fn kind_code(kind: TetrominoKind) -> i32 {
+        match kind {
+            TetrominoKind::I => 1,
+            TetrominoKind::L => 2,
+            TetrominoKind::O => 3,
+            TetrominoKind::Z => 4,
+            TetrominoKind::T => 5,
+            TetrominoKind::J => 6,
+            TetrominoKind::S => 7,
+        }
+    }
+
+    fn code(color: Color) -> i32 {
+        match color {
+            Color::Cyan => 1,
+            Color::Orange => 2,
+            Color::Yellow => 3,
+            Color::Red => 4,
+            Color::Purple => 5,
+            Color::Blue => 6,
+            Color::Green => 7,
+            Color::Grey => 8,
+        }
+    }
+
+    fn color(code: i32) -> Option<Color> {
+        match code {
+            1 => Some(Color::Cyan),
+            2 => Some(Color::Orange),
+            3 => Some(Color::Yellow),
+            4 => Some(Color::Red),
+            5 => Some(Color::Purple),
+            6 => Some(Color::Blue),
+            7 => Some(Color::Green),
+            8 => Some(Color::Grey),
+            _ => None,
+        }
+    }
+
+    fn index(x: i32, y: i32) -> usize {
+        ((TOP - 1 - y) * WIDTH + x) as usize
+    }
+
+    fn to_field(cells: &[i32]) -> Field {
+        let rows = (0..24)
+            .map(|y| {
+                (0..WIDTH)
+                    .map(|x| {
+                        if y < TOP {
+                            Self::color(cells[Self::index(x, y)])
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Field::new(rows)
+    }
+
+    // Fumen has no room for the field's topmost row, so blocks there are dropped.
+    fn from_field(field: &Field) -> Vec<i32> {
+        let mut cells = vec![0; CELLS];
+        for block in field.blocks() {
+            if *block.y() < TOP {
+                cells[Self::index(*block.x(), *block.y())] = Self::code(*block.color());
+            }
+        }
+        cells
+    }
+
+    // Clears full rows of the playfield; the garbage row below the floor never clears.
+    fn clear_lines(cells: &mut Vec<i32>) {
+        let width = WIDTH as usize;
+        let (field, garbage) = cells.split_at(CELLS - width);
+        let rows: Vec<&[i32]> = field.chunks(width).filter(|row| row.contains(&0)).collect();
+        let mut cleared = vec![0; CELLS - width - rows.len() * width];
+        for row in rows {
+            cleared.extend_from_slice(row);
+        }
+        cleared.extend_from_slice(garbage);
+        *cells = cleared;
+    }
+
+    // Comments are stored percent-encoded the way JavaScript's `escape` does it.
+    fn escape(text: &str) -> String {
+        let mut escaped = String::new();
+        for unit in text.encode_utf16() {
+            match char::from_u32(unit as u32) {
+                Some(c) if c.is_ascii_alphanumeric() || "@*_+-./".contains(c) => escaped.push(c),
+                _ if unit < 256 => escaped.push_str(&format!("%{:02X}", unit)),
+                _ => escaped.push_str(&format!("%u{:04X}", unit)),
+            }
+        }
+        escaped
+    }
+
+    fn unescape(text: &str) -> String {
+        let mut units = Vec::new();
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            let unicode = rest.strip_prefix("%u").and_then(|hex| hex.get(..4));
+            let byte = rest.strip_prefix('%').and_then(|hex| hex.get(..2));
+            if let Some(unit) = unicode.and_then(|hex| u16::from_str_radix(hex, 16).ok()) {
+                units.push(unit);
+                rest = &rest[6..];
+            } else if let Some(unit) = byte.and_then(|hex| u16::from_str_radix(hex, 16).ok()) {
+                units.push(unit);
+                rest = &rest[3..];
+            } else {
+                let mut buffer = [0; 2];
+                units.extend_from_slice(c.encode_utf16(&mut buffer));
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        String::from_utf16_lossy(&units)
+    }
+}
+
+// Base64 digits, least significant first, the way fumen packs its numbers.
+struct Values(Vec<i32>);
+
+impl Values {
+    fn decode(data: &str) -> Result<Self, String> {
+        data.chars()
+            .filter(|c| *c != '?' && !c.is_whitespace())
+            .map(|c| {
+                TABLE
+                    .find(c)
+                    .map(|idx| idx as i32)
+                    .ok_or(format!("unknown character `{}`", c))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|mut digits| {
+                digits.reverse();
+                Self(digits)
+            })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn poll(&mut self, digits: usize) -> Result<i32, String> {
+        let mut value = 0;
+        for idx in 0..digits {
+            let digit = self.0.pop().ok_or("unexpected end of fumen data")?;
+            value += digit << (6 * idx);
+        }
+        Ok(value)
+    }
+
+    fn push(&mut self, mut value: i32, digits: usize) {
+        for _ in 0..digits {
+            self.0.push(value % 64);
+            value /= 64;
+        }
+    }
+
+    // Viewers expect a `?` after the first 42 characters and then after every 47.
+    fn encode(&self) -> String {
+        let text: String = self
+            .0
+            .iter()
+            .map(|digit| TABLE.as_bytes()[*digit as usize] as char)
+            .collect();
+        if text.len() <= 42 {
+            return text;
+        }
+        let (head, tail) = text.split_at(42);
+        let mut chunks = vec![head];
+        let mut tail = tail;
+        while !tail.is_empty() {
+            let (chunk, rest) = tail.split_at(tail.len().min(47));
+            chunks.push(chunk);
+            tail = rest;
+        }
+        chunks.join("?")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        rules::Rules,
+        tetromino::{RotateDirection, TetrominoKind},
+    };
+
+    #[test]
+    fn test_decode_empty() {
+        let fumen = Fumen::decode("v115@vhAAgH").unwrap();
+        assert_eq!(1, fumen.pages().len());
+        let page = &fumen.pages()[0];
+        assert!(page.field().blocks().is_empty());
+        assert_eq!(&None, page.piece());
+        assert_eq!("", page.comment());
+        assert_eq!("v115@vhAAgH", fumen.encode());
+    }
+
+    #[test]
+    fn test_decode_error() {
+        assert!(Fumen::decode("v110@7eEuB").is_err());
+        assert!(Fumen::decode("v115@vh").is_err());
+        assert!(Fumen::decode("v115@vhAAg!").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let field = build_field();
+        let t = build_piece(TetrominoKind::T, 2, 1, TetrominoDirection::Left);
+        let i = build_piece(TetrominoKind::I, 4, 3, TetrominoDirection::Right);
+        let mut cleared = field.clone();
+        cleared.fix_blocks(t.blocks().clone());
+        cleared.clear_blocks();
+        let fumen = Fumen::new(vec![
+            Page::new(field.clone(), Some(t), "TSD ぷよ 100%".to_string()),
+            Page::new(cleared.clone(), Some(i), "TSD ぷよ 100%".to_string()),
+            Page::new(cleared.clone(), None, String::new()),
+            Page::new(cleared.clone(), None, String::new()),
+            Page::new(cleared, None, String::new()),
+        ]);
+        let text = fumen.encode();
+        assert!(text.starts_with("v115@"));
+        assert_eq!(
+            1,
+            Fumen::decode(&text).unwrap().pages()[1]
+                .field()
+                .blocks()
+                .len()
+        );
+        assert_eq!(fumen, Fumen::decode(&text).unwrap());
+        assert_eq!(
+            fumen,
+            Fumen::decode(&format!("https://fumen.zui.jp/?{}", text)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_pieces() {
+        let rules = Rules::modern();
+        let field = Field::new(vec![vec![None; 10]; 24]);
+        for kind in [
+            TetrominoKind::I,
+            TetrominoKind::J,
+            TetrominoKind::L,
+            TetrominoKind::O,
+            TetrominoKind::S,
+            TetrominoKind::T,
+            TetrominoKind::Z,
+        ]
+        .iter()
+        {
+            let mut tetromino = rules.build_tetromino(*kind);
+            tetromino.shift(0, -10);
+            for _ in 0..4 {
+                let mut blocks = tetromino.blocks();
+                blocks.sort_by_key(|block| (*block.y(), *block.x()));
+                let piece = Placement::new(*kind, tetromino.direction(), blocks, Vec::new());
+                let text = Fumen::new(vec![Page::new(
+                    field.clone(),
+                    Some(piece.clone()),
+                    String::new(),
+                )])
+                .encode();
+                let decoded = Fumen::decode(&text).unwrap();
+                let decoded = decoded.pages()[0].piece().as_ref().unwrap();
+                assert_eq!(piece.blocks(), decoded.blocks());
+                tetromino.rotate(RotateDirection::Right);
+            }
+        }
+    }
+
+    #[test]
+    fn test_split() {
+        let comment = "A".repeat(100);
+        let text = Fumen::new(vec![Page::new(build_field(), None, comment.clone())]).encode();
+        let chunks: Vec<_> = text["v115@".len()..].split('?').collect();
+        assert!(chunks.len() > 2);
+        assert_eq!(42, chunks[0].len());
+        assert_eq!(47, chunks[1].len());
+        assert_eq!(&comment, Fumen::decode(&text).unwrap().pages()[0].comment());
+    }
+
+    #[test]
+    fn test_escape() {
+        let text = "a b%ぷ";
+        assert_eq!("a%20b%25%u3077", Fumen::escape(text));
+        assert_eq!(text, Fumen::unescape(&Fumen::escape(text)));
+    }
+
+    fn build_field() -> Field {
+        let mut rows = vec![vec![None; 10]; 24];
+        for (y, row) in rows.iter_mut().take(2).enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                if !(1..=3).contains(&x) || (y == 0 && x != 2) {
+                    *cell = Some(Color::Grey);
+                }
+            }
+        }
+        rows[2][3] = Some(Color::Green);
+        Field::new(rows)
+    }
+
+    fn build_piece(kind: TetrominoKind, x: i32, y: i32, dir: TetrominoDirection) -> Placement {
+        let rotation = (0..4)
+            .find(|rotation| Fumen::direction(*rotation) == dir)
+            .unwrap();
+        let color = Fumen::color(Fumen::kind_code(kind)).unwrap();
+        let mut blocks: Vec<_> = Fumen::shape(kind, rotation)
+            .iter()
+            .map(|(dx, dy)| Block::new(color, x + dx, y + dy))
+            .collect();
+        blocks.sort_by_key(|block| (*block.y(), *block.x()));
+        Placement::new(kind, dir, blocks, Vec::new())
+    }
+}
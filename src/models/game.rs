@@ -0,0 +1,646 @@
+use crate::models::{
+    combo::Combo,
+    field::Field,
+    finesse::Finesse,
+    game_mode::GameMode,
+    hold::Hold,
+    input::{Action, Input},
+    phase::Phase,
+    rules::Rules,
+    stats::Stats,
+    tetromino::{big::SCALE, MoveDirection, RotateDirection, Tetromino, TetrominoKind},
+    tetromino_factory::TetrominoFactory,
+};
+
+use std::collections::HashMap;
+
+const ACTIONS: [Action; 8] = [
+    Action::Hold,
+    Action::RotateLeft,
+    Action::RotateRight,
+    Action::RotateHalf,
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::SoftDrop,
+    Action::HardDrop,
+];
+
+pub struct Game {
+    field: Field,
+    tetromino_factory: TetrominoFactory,
+    tetromino: Box<dyn Tetromino>,
+    hold: Hold,
+    input: Input,
+    held_frames: HashMap<Action, i32>,
+    phase: Phase,
+    rotated: bool,
+    fall_frames: i32,
+    lock_frames: i32,
+    finesse: Finesse,
+    presses: i32,
+    soft_dropped: bool,
+    combo: Combo,
+    stats: Stats,
+    rules: Rules,
+    mode: Box<dyn GameMode>,
+}
+
+impl Game {
+    pub fn new(mode: Box<dyn GameMode>) -> Self {
+        let rules = mode.rules();
+        Self::with_rules(mode, rules)
+    }
+
+    pub fn with_rules(mode: Box<dyn GameMode>, rules: Rules) -> Self {
+        let tetromino_factory = match mode.queue() {
+            Some(kinds) => TetrominoFactory::with_queue(&rules, &kinds),
+            None => TetrominoFactory::with_rules(&rules),
+        };
+        // A placeholder until the first piece comes through `spawn` like every later one.
+        let tetromino = rules.build_tetromino(TetrominoKind::I);
+        let mut game = Self {
+            field: mode.initial_field(),
+            tetromino_factory,
+            tetromino,
+            hold: Hold::new(),
+            input: Input::default(),
+            held_frames: HashMap::new(),
+            phase: Phase::Falling,
+            rotated: false,
+            fall_frames: 0,
+            lock_frames: 0,
+            finesse: Finesse::new(&rules),
+            presses: 0,
+            soft_dropped: false,
+            combo: Combo::new(),
+            stats: Stats::with_scoring(*rules.scoring(), *rules.start_level()),
+            rules,
+            mode,
+        };
+        game.spawn();
+        game
+    }
+
+    pub fn key_event(&mut self, input: Input) {
+        for action in ACTIONS.iter() {
+            if input.is_pressed(*action) {
+                let frames = self.held_frames.entry(*action).or_insert(0);
+                *frames += 1;
+                if *frames == 1 {
+                    self.count_press(*action);
+                }
+            } else {
+                self.held_frames.remove(action);
+            }
+        }
+        self.input = input;
+
+        for action in ACTIONS.iter() {
+            if !self.phase.is_falling() {
+                return;
+            }
+            if let Some(frames) = self.held_frames.get(action) {
+                if self.rules.is_triggered(*action, *frames) {
+                    self.act(*action);
+                }
+            }
+        }
+    }
+
+    pub fn update(&mut self) {
+        if self.phase.is_finished() {
+            return;
+        }
+
+        self.phase.tick();
+        self.stats.tick();
+        self.mode.on_frame(&mut self.field, &self.stats);
+        if self.phase.is_falling() && !self.push_up() {
+            self.stats.top_out();
+            self.phase = Phase::Finished;
+            return;
+        }
+
+        if self.mode.is_finished(&self.field, &self.stats) {
+            self.phase = Phase::Finished;
+            return;
+        }
+
+        let delay = self.mode.delay(&self.stats);
+
+        if self.phase.is_falling() {
+            let gravity = self.mode.gravity(&self.stats);
+            let mut landed = false;
+            self.fall_frames += gravity.rows();
+            while self.fall_frames >= *gravity.frames() {
+                self.fall_frames -= gravity.frames();
+                if !self.act(Action::SoftDrop) {
+                    self.fall_frames = 0;
+                    landed = true;
+                    break;
+                }
+            }
+            if self.is_grounded() {
+                self.lock_frames += 1;
+            }
+            // A lock delay of zero locks on the gravity tick that fails to move the piece.
+            let locks = if *delay.lock() == 0 {
+                landed
+            } else {
+                self.is_grounded() && self.lock_frames >= *delay.lock()
+            };
+            if !locks {
+                return;
+            }
+            self.lock();
+        }
+
+        if let Phase::LineClear(_, frames) = self.phase {
+            if frames < *delay.line_clear() {
+                return;
+            }
+            self.clear();
+        }
+
+        if let Phase::Entry(frames) = self.phase {
+            if frames < *delay.are() {
+                return;
+            }
+            self.spawn();
+        }
+    }
+
+    pub fn field(&self) -> &Field {
+        &self.field
+    }
+
+    pub fn tetromino(&self) -> &dyn Tetromino {
+        self.tetromino.as_ref()
+    }
+
+    pub fn hold(&self) -> &Hold {
+        &self.hold
+    }
+
+    pub fn phase(&self) -> &Phase {
+        &self.phase
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn mode(&self) -> &dyn GameMode {
+        self.mode.as_ref()
+    }
+
+    pub fn next_kinds(&mut self, num: usize) -> Vec<TetrominoKind> {
+        self.tetromino_factory.next_kinds(num)
+    }
+}
+
+impl Game {
+    fn act(&mut self, action: Action) -> bool {
+        match action {
+            Action::MoveLeft => self.move_(MoveDirection::Left),
+            Action::MoveRight => self.move_(MoveDirection::Right),
+            Action::SoftDrop => self.move_(MoveDirection::Down),
+            Action::RotateLeft => self.rotate(RotateDirection::Left),
+            Action::RotateRight => self.rotate(RotateDirection::Right),
+            Action::RotateHalf => self.rotate(RotateDirection::Half),
+            Action::Hold => self.hold_tetromino(),
+            Action::HardDrop => self.hard_drop(),
+        }
+    }
+
+    fn move_(&mut self, move_dir: MoveDirection) -> bool {
+        if !self.tetromino.try_move(move_dir, &self.field) {
+            return false;
+        }
+        self.rotated = false;
+        if let MoveDirection::Down = move_dir {
+            self.lock_frames = 0;
+        }
+        true
+    }
+
+    fn rotate(&mut self, rotate_dir: RotateDirection) -> bool {
+        if !self
+            .tetromino
+            .try_rotate(rotate_dir, &self.field, *self.rules.kicks())
+        {
+            return false;
+        }
+        self.rotated = true;
+        true
+    }
+
+    fn hold_tetromino(&mut self) -> bool {
+        if !*self.rules.hold() || !self.hold.can_hold() {
+            return false;
+        }
+        if self.hold.tetromino().is_none() && self.tetromino_factory.is_empty() {
+            return false;
+        }
+        self.tetromino = match self.hold.swap(self.tetromino.kind()) {
+            Some(kind) => self.rules.build_tetromino(kind),
+            None => self.tetromino_factory.pick_tetromino(),
+        };
+        self.rotated = false;
+        self.presses = 0;
+        self.soft_dropped = false;
+        true
+    }
+
+    fn hard_drop(&mut self) -> bool {
+        if !*self.rules.hard_drop() {
+            return false;
+        }
+        while self.move_(MoveDirection::Down) {}
+        self.lock();
+        true
+    }
+
+    fn count_press(&mut self, action: Action) {
+        match action {
+            Action::MoveLeft
+            | Action::MoveRight
+            | Action::RotateLeft
+            | Action::RotateRight
+            | Action::RotateHalf => self.presses += 1,
+            Action::SoftDrop => self.soft_dropped = true,
+            _ => {}
+        }
+    }
+
+    fn is_grounded(&self) -> bool {
+        !self
+            .field
+            .is_vacant(&self.tetromino.dry_move(MoveDirection::Down))
+    }
+
+    fn push_up(&mut self) -> bool {
+        for _ in 0..24 {
+            if self.field.is_vacant(&self.tetromino.blocks()) {
+                return true;
+            }
+            self.tetromino.shift(0, 1);
+        }
+        false
+    }
+
+    fn lock(&mut self) {
+        let blocks = self.tetromino.dry_move(MoveDirection::Down);
+        if !Field::can_fix(&blocks) {
+            self.stats.top_out();
+            self.phase = Phase::Finished;
+            return;
+        }
+        // Tucks and spins need a soft drop, so only pieces placed from above are judged.
+        if !self.soft_dropped && self.finesse.is_fault(self.tetromino.as_ref(), self.presses) {
+            self.stats.add_fault();
+        }
+        self.presses = 0;
+        self.soft_dropped = false;
+        self.field
+            .fix_blocks_at(self.tetromino.blocks(), *self.stats.frames());
+        self.mode
+            .on_lock(&mut self.field, self.tetromino.as_ref(), &self.stats);
+        if self.mode.take_restart() {
+            self.restart();
+            return;
+        }
+
+        let rows = self.field.filled_rows();
+        if rows.is_empty() {
+            self.clear();
+        } else {
+            self.phase = Phase::LineClear(rows, 0);
+        }
+    }
+
+    fn restart(&mut self) {
+        self.field = self.mode.initial_field();
+        self.tetromino_factory.reset_bag();
+        self.hold = Hold::new();
+        self.combo = Combo::new();
+        self.phase = Phase::Entry(0);
+    }
+
+    fn clear(&mut self) {
+        // The corner check only works on unscaled cells, so big pieces never score t-spins.
+        let t_spin = self.tetromino.kind() == TetrominoKind::T
+            && self.rotated
+            && !*self.rules.big()
+            && self.field.is_t_spin(&self.tetromino.blocks());
+        let mut clear = self.field.clear_blocks();
+        if *self.rules.big() {
+            clear = clear.scale_down(SCALE);
+        }
+        let event = self.combo.update(clear, t_spin);
+        self.stats.add_clear(event);
+        if *event.lines() > 0 {
+            self.mode.on_line_clear(&event, &self.stats);
+        }
+
+        self.phase = if self.mode.is_finished(&self.field, &self.stats) {
+            Phase::Finished
+        } else {
+            Phase::Entry(0)
+        };
+    }
+
+    fn spawn(&mut self) {
+        if self.tetromino_factory.is_empty() {
+            self.phase = Phase::Finished;
+            return;
+        }
+        self.hold.release();
+        self.tetromino = self.tetromino_factory.pick_tetromino();
+        self.rotated = false;
+        self.fall_frames = 0;
+        self.lock_frames = 0;
+        self.phase = Phase::Falling;
+
+        if self.input.is_pressed(Action::Hold) {
+            self.hold_tetromino();
+        }
+        if self.input.is_pressed(Action::RotateLeft) {
+            self.rotate(RotateDirection::Left);
+        } else if self.input.is_pressed(Action::RotateRight) {
+            self.rotate(RotateDirection::Right);
+        } else if self.input.is_pressed(Action::RotateHalf) {
+            self.rotate(RotateDirection::Half);
+        }
+        if !self.field.is_vacant(&self.tetromino.blocks()) {
+            self.stats.top_out();
+            self.phase = Phase::Finished;
+            return;
+        }
+        self.mode.on_spawn(&self.stats);
+        if self.mode.gravity(&self.stats).is_instant() {
+            while self.move_(MoveDirection::Down) {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        delay::Delay,
+        game_mode::{
+            big::Big,
+            classic::Classic,
+            drill::Drill,
+            endless::Endless,
+            master::Master,
+            puzzle::{Goal, Puzzle},
+            Record,
+        },
+        gravity::Gravity,
+        layout::Layout,
+        rotation_system::RotationSystem,
+    };
+
+    struct TwentyG;
+
+    impl GameMode for TwentyG {
+        fn name(&self) -> &str {
+            "twenty_g"
+        }
+        fn layout(&self) -> Layout {
+            Layout::standard()
+        }
+        fn gravity(&self, _stats: &Stats) -> Gravity {
+            Gravity::new(20, 1)
+        }
+        fn delay(&self, _stats: &Stats) -> Delay {
+            Delay::new(6, 20, 30)
+        }
+        fn is_finished(&self, _field: &Field, _stats: &Stats) -> bool {
+            false
+        }
+        fn record(&self, _stats: &Stats) -> Option<Record> {
+            None
+        }
+        fn result(&self, _stats: &Stats) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_update() {
+        let mut game = Game::new(Box::new(Endless));
+        let blocks = game.tetromino().blocks();
+        for _ in 0..16 {
+            game.update();
+        }
+        let moved: Vec<_> = blocks.iter().map(|block| block.shift(0, -1)).collect();
+        assert_eq!(moved, game.tetromino().blocks());
+        assert_eq!(16, *game.stats().frames());
+    }
+
+    #[test]
+    fn test_first_spawn() {
+        let game = Game::new(Box::new(Master::new(None)));
+        assert_eq!("  1/100", game.mode().status(game.stats())[1]);
+        assert!(game.phase().is_falling());
+    }
+
+    #[test]
+    fn test_key_event() {
+        let mut game = Game::new(Box::new(Endless));
+        let blocks = game.tetromino().blocks();
+        game.key_event(Input::new(vec![Action::MoveLeft]));
+        let moved: Vec<_> = blocks.iter().map(|block| block.shift(-1, 0)).collect();
+        assert_eq!(moved, game.tetromino().blocks());
+
+        let kind = game.tetromino().kind();
+        game.key_event(Input::new(vec![Action::Hold]));
+        assert_eq!(Some(&kind), game.hold().tetromino());
+    }
+
+    #[test]
+    fn test_das() {
+        let mut game = Game::new(Box::new(Classic::new(0, 1, None)));
+        let blocks = game.tetromino().blocks();
+        for _ in 0..17 {
+            game.key_event(Input::new(vec![Action::MoveLeft]));
+        }
+        let moved: Vec<_> = blocks.iter().map(|block| block.shift(-2, 0)).collect();
+        assert_eq!(moved, game.tetromino().blocks());
+    }
+
+    #[test]
+    fn test_hard_drop() {
+        let mut game = Game::new(Box::new(Endless));
+        game.key_event(Input::new(vec![Action::HardDrop]));
+        assert!(!game.phase().is_falling());
+        assert_eq!(4, game.field().blocks().len());
+
+        let mut game = Game::new(Box::new(Classic::new(0, 1, None)));
+        game.key_event(Input::new(vec![Action::HardDrop, Action::Hold]));
+        assert!(game.phase().is_falling());
+        assert_eq!(None, game.hold().tetromino());
+        assert!(game.field().blocks().is_empty());
+    }
+
+    #[test]
+    fn test_drill() {
+        let drill = Drill::parse_pack("name: A\npieces: I\nfield:\n1111......\n").unwrap();
+        let mut game = Game::new(Box::new(drill[0].clone()));
+        game.key_event(Input::new(vec![Action::Hold]));
+        game.key_event(Input::default());
+        game.key_event(Input::new(vec![Action::HardDrop]));
+        assert!(game.field().blocks().is_empty());
+        assert_eq!(None, game.hold().tetromino());
+        assert_eq!("0/1", game.mode().status(game.stats())[2]);
+        while !game.phase().is_falling() {
+            game.update();
+        }
+        let mut bag = game.next_kinds(6);
+        bag.push(game.tetromino().kind());
+        bag.sort_by_key(|kind| *kind as usize);
+        bag.dedup();
+        assert_eq!(7, bag.len());
+    }
+
+    #[test]
+    fn test_finesse() {
+        let mut game = Game::new(Box::new(Endless));
+        for action in [Action::MoveLeft; 5]
+            .iter()
+            .chain([Action::HardDrop].iter())
+        {
+            game.key_event(Input::new(vec![*action]));
+            game.key_event(Input::default());
+        }
+        assert_eq!(1, *game.stats().faults());
+
+        while !game.phase().is_falling() {
+            game.update();
+        }
+        game.key_event(Input::new(vec![Action::HardDrop]));
+        assert_eq!(2, *game.stats().pieces());
+        assert_eq!(1, *game.stats().faults());
+    }
+
+    #[test]
+    fn test_rotation_system() {
+        let rules = Rules::modern().with_rotation_system(RotationSystem::Ars);
+        let mut game = Game::with_rules(Box::new(Endless), rules);
+        let kind = game.tetromino().kind();
+        let ars = RotationSystem::Ars.build_tetromino(kind);
+        assert_eq!(ars.blocks(), game.tetromino().blocks());
+
+        game.key_event(Input::new(vec![Action::Hold]));
+        let kind = game.tetromino().kind();
+        let ars = RotationSystem::Ars.build_tetromino(kind);
+        assert_eq!(ars.blocks(), game.tetromino().blocks());
+    }
+
+    #[test]
+    fn test_instant_gravity() {
+        let mut game = Game::new(Box::new(TwentyG));
+        game.update();
+        assert!(game
+            .tetromino()
+            .blocks()
+            .iter()
+            .any(|block| *block.y() == 0));
+        for _ in 0..28 {
+            game.update();
+        }
+        assert!(game.phase().is_falling());
+        game.update();
+        assert!(!game.phase().is_falling());
+        assert_eq!(4, game.field().blocks().len());
+
+        for _ in 0..6 {
+            game.update();
+        }
+        assert!(game.phase().is_falling());
+        let blocks = game.tetromino().dry_move(MoveDirection::Down);
+        assert!(!game.field().is_vacant(&blocks));
+    }
+
+    #[test]
+    fn test_puzzle() {
+        let puzzles = Puzzle::parse_pack(include_str!("../assets/puzzles.txt")).unwrap();
+        let mut game = Game::new(Box::new(puzzles[0].clone()));
+        assert_eq!(16, game.field().blocks().len());
+        for _ in 0..4 {
+            game.key_event(Input::new(vec![Action::MoveRight]));
+            game.key_event(Input::new(Vec::new()));
+        }
+        game.key_event(Input::new(vec![Action::HardDrop]));
+        let mut frames = 0;
+        while !game.phase().is_finished() {
+            game.update();
+            frames += 1;
+            assert!(frames < 100);
+        }
+        assert!(game.field().blocks().is_empty());
+        assert_eq!("SOLVED", game.mode().result(game.stats())[0]);
+    }
+
+    #[test]
+    fn test_big() {
+        let mut game = Game::new(Box::new(Big::new(None)));
+        let blocks = game.tetromino().blocks();
+        assert_eq!(16, blocks.len());
+        game.key_event(Input::new(vec![Action::MoveLeft]));
+        let moved: Vec<_> = blocks.iter().map(|block| block.shift(-2, 0)).collect();
+        assert_eq!(moved, game.tetromino().blocks());
+        game.key_event(Input::new(vec![Action::HardDrop]));
+        assert_eq!(16, game.field().blocks().len());
+        assert!(game.field().blocks().iter().any(|block| *block.y() == 0));
+    }
+
+    #[test]
+    fn test_big_t_spin() {
+        let puzzle = Puzzle::new(
+            "A".to_string(),
+            Field::new(vec![vec![None; 10]; 24]),
+            vec![TetrominoKind::T, TetrominoKind::I],
+            Goal::Lines(1),
+        );
+        let mut game = Game::with_rules(Box::new(puzzle), Rules::modern().with_big(true));
+        for _ in 0..2 {
+            game.key_event(Input::new(vec![Action::RotateRight]));
+            game.key_event(Input::default());
+        }
+        while game
+            .field()
+            .is_vacant(&game.tetromino().dry_move(MoveDirection::Down))
+        {
+            game.update();
+        }
+        for action in [Action::RotateRight, Action::RotateLeft].iter() {
+            game.key_event(Input::new(vec![*action]));
+            game.key_event(Input::default());
+        }
+        while *game.stats().pieces() == 0 {
+            game.update();
+        }
+        assert_eq!(None, game.stats().last_event());
+    }
+
+    #[test]
+    fn test_top_out() {
+        let mut game = Game::new(Box::new(Endless));
+        let mut frames = 0;
+        while !game.phase().is_finished() {
+            game.update();
+            frames += 1;
+            assert!(frames < 100000);
+        }
+        assert!(game.stats().pieces() > &0);
+        assert!(game.stats().topped_out());
+    }
+}
@@ -0,0 +1,97 @@
+pub mod big;
+pub mod classic;
+pub mod dig;
+pub mod drill;
+pub mod endless;
+pub mod marathon;
+pub mod master;
+pub mod puzzle;
+pub mod roll;
+pub mod sprint;
+pub mod ultra;
+pub mod zen;
+
+use crate::models::{
+    block::Block,
+    combo::ClearEvent,
+    delay::Delay,
+    field::Field,
+    gravity::Gravity,
+    layout::Layout,
+    rules::Rules,
+    stats::Stats,
+    tetromino::{Tetromino, TetrominoKind},
+    visibility::Visibility,
+};
+
+pub trait GameMode {
+    fn name(&self) -> &str;
+    fn layout(&self) -> Layout;
+    fn rules(&self) -> Rules {
+        Rules::modern()
+    }
+    fn initial_field(&self) -> Field {
+        Field::new(vec![vec![None; 10]; 24])
+    }
+    fn queue(&self) -> Option<Vec<TetrominoKind>> {
+        None
+    }
+    fn gravity(&self, stats: &Stats) -> Gravity;
+    fn visibility(&self) -> Visibility {
+        Visibility::Visible
+    }
+    fn delay(&self, _stats: &Stats) -> Delay {
+        *self.rules().delay()
+    }
+    fn on_spawn(&mut self, _stats: &Stats) {}
+    fn on_lock(&mut self, _field: &mut Field, _tetromino: &dyn Tetromino, _stats: &Stats) {}
+    fn on_line_clear(&mut self, _event: &ClearEvent, _stats: &Stats) {}
+    fn on_frame(&mut self, _field: &mut Field, _stats: &Stats) {}
+    // Asks the game to start over from the initial field with a fresh bag.
+    fn take_restart(&mut self) -> bool {
+        false
+    }
+    fn target(&self) -> Vec<Block> {
+        Vec::new()
+    }
+    fn is_finished(&self, field: &Field, stats: &Stats) -> bool;
+    fn record(&self, stats: &Stats) -> Option<Record>;
+    fn result(&self, stats: &Stats) -> Vec<String>;
+    fn status(&self, _stats: &Stats) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Record {
+    Time(i32),
+    Score(i32),
+}
+
+impl Record {
+    pub fn value(&self) -> i32 {
+        match self {
+            Record::Time(value) | Record::Score(value) => *value,
+        }
+    }
+
+    pub fn is_better_than(&self, value: i32) -> bool {
+        match self {
+            Record::Time(time) => *time < value,
+            Record::Score(score) => *score > value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_better_than() {
+        assert!(Record::Time(1000).is_better_than(1001));
+        assert!(!Record::Time(1000).is_better_than(1000));
+        assert!(Record::Score(1000).is_better_than(999));
+        assert!(!Record::Score(1000).is_better_than(1001));
+    }
+}
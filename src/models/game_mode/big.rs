@@ -0,0 +1,85 @@
+use crate::models::{
+    field::Field,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    layout::{HudItem, Layout},
+    rules::Rules,
+    stats::Stats,
+};
+
+const GOAL: i32 = 100;
+const GRAVITY: [i32; 10] = [60, 48, 37, 28, 21, 16, 11, 8, 6, 4];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Big {
+    personal_best: Option<i32>,
+}
+
+impl GameMode for Big {
+    fn name(&self) -> &str {
+        "big"
+    }
+    fn layout(&self) -> Layout {
+        Layout::new(vec![
+            HudItem::Hold,
+            HudItem::Next,
+            HudItem::Score,
+            HudItem::Level,
+            HudItem::Goal(GOAL),
+            HudItem::Time,
+        ])
+    }
+    fn rules(&self) -> Rules {
+        Rules::modern().with_big(true)
+    }
+    fn gravity(&self, stats: &Stats) -> Gravity {
+        Gravity::frames_per_row(GRAVITY[(stats.level().min(10) - 1) as usize])
+    }
+    fn is_finished(&self, _field: &Field, stats: &Stats) -> bool {
+        *stats.lines() >= GOAL
+    }
+    fn record(&self, stats: &Stats) -> Option<Record> {
+        Some(Record::Score(*stats.score()))
+    }
+    fn result(&self, stats: &Stats) -> Vec<String> {
+        let score = *stats.score();
+        vec![
+            if *stats.lines() >= GOAL {
+                "CONGRATULATIONS".to_string()
+            } else {
+                "GAME OVER".to_string()
+            },
+            format!("SCORE {}", score),
+            format!("LINES {}", stats.lines()),
+            match self.personal_best {
+                Some(best) if score <= best => format!("PB {}", best),
+                _ => "NEW PB".to_string(),
+            },
+        ]
+    }
+}
+
+impl Big {
+    pub fn new(personal_best: Option<i32>) -> Self {
+        Self { personal_best }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{clear::Clear, combo::Combo};
+
+    #[test]
+    fn test_gravity() {
+        let big = Big::new(None);
+        let mut stats = Stats::new();
+        assert_eq!(Gravity::frames_per_row(60), big.gravity(&stats));
+        let mut combo = Combo::new();
+        for _ in 0..30 {
+            stats.add_clear(combo.update(Clear::new(4, false), false));
+        }
+        assert_eq!(Gravity::frames_per_row(4), big.gravity(&stats));
+        assert!(*big.rules().big());
+    }
+}
@@ -0,0 +1,103 @@
+use crate::models::{
+    field::Field,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    layout::{HudItem, Layout},
+    rules::Rules,
+    stats::Stats,
+};
+
+const GRAVITY: [i32; 30] = [
+    48, 43, 38, 33, 28, 23, 18, 13, 8, 6, 5, 5, 5, 4, 4, 4, 3, 3, 3, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+    1,
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Classic {
+    start_level: i32,
+    seed: u32,
+    personal_best: Option<i32>,
+}
+
+impl GameMode for Classic {
+    fn name(&self) -> &str {
+        "classic"
+    }
+    fn layout(&self) -> Layout {
+        Layout::new(vec![
+            HudItem::Next,
+            HudItem::Score,
+            HudItem::Level,
+            HudItem::Lines,
+            HudItem::Time,
+        ])
+    }
+    fn rules(&self) -> Rules {
+        Rules::classic(self.start_level, self.seed)
+    }
+    fn gravity(&self, stats: &Stats) -> Gravity {
+        Gravity::frames_per_row(GRAVITY[stats.level().min(29) as usize])
+    }
+    fn is_finished(&self, _field: &Field, _stats: &Stats) -> bool {
+        false
+    }
+    fn record(&self, stats: &Stats) -> Option<Record> {
+        Some(Record::Score(*stats.score()))
+    }
+    fn result(&self, stats: &Stats) -> Vec<String> {
+        let score = *stats.score();
+        vec![
+            "GAME OVER".to_string(),
+            format!("SCORE {}", score),
+            format!("LEVEL {}", stats.level()),
+            format!("LINES {}", stats.lines()),
+            match self.personal_best {
+                Some(best) if score <= best => format!("PB {}", best),
+                _ => "NEW PB".to_string(),
+            },
+        ]
+    }
+}
+
+impl Classic {
+    pub fn new(start_level: i32, seed: u32, personal_best: Option<i32>) -> Self {
+        Self {
+            start_level,
+            seed,
+            personal_best,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{clear::Clear, combo::Combo, stats::Scoring};
+
+    #[test]
+    fn test_gravity() {
+        let classic = Classic::new(0, 1, None);
+        let mut stats = Stats::with_scoring(Scoring::Nes, 0);
+        assert_eq!(Gravity::frames_per_row(48), classic.gravity(&stats));
+        let mut combo = Combo::new();
+        for _ in 0..5 {
+            stats.add_clear(combo.update(Clear::new(2, false), false));
+        }
+        assert_eq!(Gravity::frames_per_row(43), classic.gravity(&stats));
+
+        let stats = Stats::with_scoring(Scoring::Nes, 29);
+        assert_eq!(Gravity::frames_per_row(1), classic.gravity(&stats));
+        let stats = Stats::with_scoring(Scoring::Nes, 35);
+        assert_eq!(Gravity::frames_per_row(1), classic.gravity(&stats));
+    }
+
+    #[test]
+    fn test_rules() {
+        let rules = Classic::new(9, 1, None).rules();
+        assert!(!*rules.hold());
+        assert!(!*rules.hard_drop());
+        assert!(!*rules.kicks());
+        assert_eq!(1, *rules.preview());
+        assert_eq!(9, *rules.start_level());
+    }
+}
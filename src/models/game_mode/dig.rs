@@ -0,0 +1,207 @@
+use crate::models::{
+    block::Color,
+    field::Field,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    hud::Hud,
+    layout::{HudItem, Layout},
+    rng::Rng,
+    stats::Stats,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Dig {
+    initial: i32,
+    total: i32,
+    interval: i32,
+    messiness: u32,
+    inserted: i32,
+    started: bool,
+    hole: i32,
+    topped_out: bool,
+    rng: Rng,
+}
+
+impl GameMode for Dig {
+    fn name(&self) -> &str {
+        "dig"
+    }
+    fn layout(&self) -> Layout {
+        Layout::new(vec![
+            HudItem::Hold,
+            HudItem::Next,
+            HudItem::Time,
+            HudItem::Lines,
+            HudItem::Pps,
+            HudItem::Action,
+        ])
+    }
+    fn gravity(&self, _stats: &Stats) -> Gravity {
+        Gravity::frames_per_row(60)
+    }
+    fn on_frame(&mut self, field: &mut Field, stats: &Stats) {
+        if !self.started {
+            self.started = true;
+            for _ in 0..self.initial {
+                self.rise(field);
+            }
+        } else if self.inserted < self.total && stats.frames() % self.interval == 0 {
+            self.rise(field);
+        }
+    }
+    fn is_finished(&self, field: &Field, _stats: &Stats) -> bool {
+        self.topped_out || (self.inserted >= self.total && !field.contains(Color::Grey))
+    }
+    fn record(&self, stats: &Stats) -> Option<Record> {
+        if self.topped_out || *stats.topped_out() || self.inserted < self.total {
+            None
+        } else {
+            Some(Record::Time(stats.elapsed_millis()))
+        }
+    }
+    fn result(&self, stats: &Stats) -> Vec<String> {
+        match self.record(stats) {
+            Some(record) => vec![
+                "FINISHED".to_string(),
+                format!("TIME {}", Hud::format_millis(record.value())),
+                format!("PIECES {}", stats.pieces()),
+            ],
+            _ => vec![
+                "GAME OVER".to_string(),
+                format!("GARBAGE {}/{}", self.inserted, self.total),
+            ],
+        }
+    }
+}
+
+impl Dig {
+    pub fn new(initial: i32, total: i32, interval: i32, messiness: u32, seed: u32) -> Self {
+        let mut rng = Rng::new(seed);
+        let hole = rng.below(10) as i32;
+        Self {
+            initial,
+            total: total.max(initial),
+            interval: interval.max(1),
+            messiness,
+            inserted: 0,
+            started: false,
+            hole,
+            topped_out: false,
+            rng,
+        }
+    }
+}
+
+impl Dig {
+    fn rise(&mut self, field: &mut Field) {
+        if self.rng.below(100) < self.messiness {
+            self.hole = (self.hole + 1 + self.rng.below(9) as i32) % 10;
+        }
+        self.topped_out |= !field.insert_garbage(&[self.hole]);
+        self.inserted += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_on_frame() {
+        let mut dig = Dig::new(4, 6, 10, 0, 1);
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        let mut stats = Stats::new();
+        stats.tick();
+        dig.on_frame(&mut field, &stats);
+        assert_eq!(40 - 4, field.blocks().len());
+        for _ in 0..9 {
+            stats.tick();
+            dig.on_frame(&mut field, &stats);
+        }
+        assert_eq!(50 - 5, field.blocks().len());
+        for _ in 0..20 {
+            stats.tick();
+            dig.on_frame(&mut field, &stats);
+        }
+        assert_eq!(60 - 6, field.blocks().len());
+        assert!(!dig.is_finished(&field, &stats));
+    }
+
+    #[test]
+    fn test_on_frame_without_initial() {
+        let mut dig = Dig::new(0, 2, 5, 0, 1);
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        let mut stats = Stats::new();
+        for _ in 0..10 {
+            stats.tick();
+            dig.on_frame(&mut field, &stats);
+        }
+        assert_eq!(20 - 2, field.blocks().len());
+    }
+
+    #[test]
+    fn test_messiness() {
+        let mut dig = Dig::new(10, 10, 1, 0, 7);
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        dig.on_frame(&mut field, &Stats::new());
+        let holes: Vec<_> = field
+            .blocks()
+            .iter()
+            .filter(|block| *block.y() == 0)
+            .map(|block| *block.x())
+            .collect();
+        for y in 1..10 {
+            let row: Vec<_> = field
+                .blocks()
+                .iter()
+                .filter(|block| *block.y() == y)
+                .map(|block| *block.x())
+                .collect();
+            assert_eq!(holes, row);
+        }
+
+        let mut dig = Dig::new(10, 10, 1, 100, 7);
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        dig.on_frame(&mut field, &Stats::new());
+        let lower: Vec<_> = field
+            .blocks()
+            .iter()
+            .filter(|block| *block.y() == 0)
+            .map(|block| *block.x())
+            .collect();
+        let upper: Vec<_> = field
+            .blocks()
+            .iter()
+            .filter(|block| *block.y() == 1)
+            .map(|block| *block.x())
+            .collect();
+        assert_ne!(lower, upper);
+    }
+
+    #[test]
+    fn test_is_finished() {
+        let mut dig = Dig::new(1, 1, 1, 0, 1);
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        let stats = Stats::new();
+        dig.on_frame(&mut field, &stats);
+        assert!(!dig.is_finished(&field, &stats));
+        let field = Field::new(vec![vec![None; 10]; 24]);
+        assert!(dig.is_finished(&field, &stats));
+        assert_eq!("FINISHED", dig.result(&stats)[0]);
+    }
+
+    #[test]
+    fn test_record() {
+        let mut dig = Dig::new(1, 2, 1, 0, 1);
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        let mut stats = Stats::new();
+        dig.on_frame(&mut field, &stats);
+        assert_eq!(None, dig.record(&stats));
+        stats.tick();
+        dig.on_frame(&mut field, &stats);
+        assert!(dig.record(&stats).is_some());
+        stats.top_out();
+        assert_eq!(None, dig.record(&stats));
+        assert_eq!("GAME OVER", dig.result(&stats)[0]);
+    }
+}
@@ -0,0 +1,457 @@
+use crate::models::{
+    block::{Block, Color},
+    field::Field,
+    fumen::Fumen,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    layout::{HudItem, Layout},
+    stats::Stats,
+    tetromino::{Tetromino, TetrominoKind},
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Drill {
+    name: String,
+    field: Field,
+    targets: Vec<(TetrominoKind, Vec<Block>)>,
+    placed: usize,
+    attempts: i32,
+    completed: i32,
+    restart: bool,
+}
+
+impl GameMode for Drill {
+    fn name(&self) -> &str {
+        "drill"
+    }
+    fn layout(&self) -> Layout {
+        Layout::new(vec![
+            HudItem::Hold,
+            HudItem::Next,
+            HudItem::Status,
+            HudItem::Finesse,
+            HudItem::Time,
+        ])
+    }
+    fn initial_field(&self) -> Field {
+        self.field.clone()
+    }
+    fn gravity(&self, _stats: &Stats) -> Gravity {
+        Gravity::frames_per_row(60)
+    }
+    fn on_lock(&mut self, _field: &mut Field, tetromino: &dyn Tetromino, _stats: &Stats) {
+        let is_target = match self.targets.get(self.placed) {
+            Some((kind, blocks)) => {
+                *kind == tetromino.kind() && Self::is_same_cells(blocks, &tetromino.blocks())
+            }
+            None => false,
+        };
+        if !is_target {
+            self.restart();
+            return;
+        }
+        self.placed += 1;
+        if self.placed == self.targets.len() {
+            self.completed += 1;
+            self.restart();
+        }
+    }
+    fn take_restart(&mut self) -> bool {
+        std::mem::take(&mut self.restart)
+    }
+    fn target(&self) -> Vec<Block> {
+        self.targets
+            .get(self.placed)
+            .map(|(_, blocks)| blocks.clone())
+            .unwrap_or_default()
+    }
+    fn is_finished(&self, _field: &Field, _stats: &Stats) -> bool {
+        false
+    }
+    fn record(&self, _stats: &Stats) -> Option<Record> {
+        None
+    }
+    fn result(&self, _stats: &Stats) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            format!("CLEARED {}/{}", self.completed, self.attempts),
+        ]
+    }
+    fn status(&self, _stats: &Stats) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            "CLEARED".to_string(),
+            format!("{}/{}", self.completed, self.attempts),
+        ]
+    }
+}
+
+impl Drill {
+    pub fn new(name: String, field: Field, targets: Vec<(TetrominoKind, Vec<Block>)>) -> Self {
+        Self {
+            name,
+            field,
+            targets,
+            placed: 0,
+            attempts: 0,
+            completed: 0,
+            restart: false,
+        }
+    }
+
+    pub fn parse_pack(text: &str) -> Result<Vec<Self>, String> {
+        let mut drills = Vec::new();
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim()))
+            .filter(|(_, line)| !line.starts_with('#'))
+            .peekable();
+
+        while lines.peek().is_some() {
+            let mut name = None;
+            let mut pieces = None;
+            let mut rows = Vec::new();
+            let mut fumen = None;
+            while let Some((number, line)) = lines.next() {
+                if line.is_empty() {
+                    break;
+                }
+                let (key, value) = match line.find(':') {
+                    Some(idx) => (&line[..idx], line[idx + 1..].trim()),
+                    None => return Err(format!("line {}: expected `key: value`", number)),
+                };
+                match key {
+                    "name" => name = Some(value.to_string()),
+                    "pieces" => pieces = Some(Self::parse_pieces(value, number)?),
+                    "fumen" => fumen = Some(Self::parse_fumen(value, number)?),
+                    "field" => {
+                        while let Some((number, row)) = lines.next_if(|(_, line)| !line.is_empty())
+                        {
+                            rows.push(Self::parse_row(row, number)?);
+                        }
+                    }
+                    _ => return Err(format!("line {}: unknown key `{}`", number, key)),
+                }
+            }
+
+            if name.is_none() && pieces.is_none() && rows.is_empty() && fumen.is_none() {
+                continue;
+            }
+            let name = name.ok_or("opener without a name")?;
+            if rows.len() > 20 {
+                return Err(format!("{}: field is higher than 20 rows", name));
+            }
+            let drill = match fumen {
+                Some(_) if pieces.is_some() || !rows.is_empty() => {
+                    return Err(format!("{}: fumen is given with pieces or field", name));
+                }
+                Some(fumen) => {
+                    let field = fumen.pages()[0].field().clone();
+                    if field.blocks().iter().any(|block| *block.y() >= 20) {
+                        return Err(format!("{}: field is higher than 20 rows", name));
+                    }
+                    // Every page with a piece is the next step, in the frame of that page's field.
+                    let targets = fumen
+                        .pages()
+                        .iter()
+                        .filter_map(|page| page.piece().as_ref())
+                        .map(|placement| (*placement.kind(), placement.blocks().clone()))
+                        .collect();
+                    Self::new(name, field, targets)
+                }
+                None => {
+                    let pieces = pieces.ok_or(format!("{}: missing pieces", name))?;
+                    let mut cells = vec![Vec::new(); pieces.len()];
+                    for (y, row) in rows.iter().rev().enumerate() {
+                        for (x, step) in row.iter().enumerate() {
+                            if let Some(step) = step {
+                                cells
+                                    .get_mut(*step)
+                                    .ok_or(format!("{}: no piece for step {}", name, step + 1))?
+                                    .push((x as i32, y as i32));
+                            }
+                        }
+                    }
+                    if let Some(step) = cells.iter().position(|cells| cells.len() != 4) {
+                        return Err(format!("{}: step {} must cover four cells", name, step + 1));
+                    }
+                    let field = Field::new(vec![vec![None; 10]; 24]);
+                    Self::new(name, field, Self::build_targets(&pieces, &cells))
+                }
+            };
+            drills.push(drill);
+        }
+        Ok(drills)
+    }
+
+    pub fn title(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Drill {
+    fn restart(&mut self) {
+        self.placed = 0;
+        self.attempts += 1;
+        self.restart = true;
+    }
+
+    fn is_same_cells(target: &[Block], blocks: &[Block]) -> bool {
+        target.len() == blocks.len()
+            && blocks.iter().all(|block| {
+                target
+                    .iter()
+                    .any(|cell| cell.x() == block.x() && cell.y() == block.y())
+            })
+    }
+
+    // The field shows every step at once, so rows completed by earlier steps are taken out
+    // of the later targets, as the game clears them before those pieces arrive.
+    fn build_targets(
+        pieces: &[TetrominoKind],
+        cells: &[Vec<(i32, i32)>],
+    ) -> Vec<(TetrominoKind, Vec<Block>)> {
+        let mut filled = [0; 20];
+        let mut cleared: Vec<i32> = Vec::new();
+        pieces
+            .iter()
+            .zip(cells.iter())
+            .map(|(kind, cells)| {
+                let color = Self::color(*kind);
+                let blocks = cells
+                    .iter()
+                    .map(|(x, y)| {
+                        let below = cleared.iter().filter(|row| *row < y).count() as i32;
+                        Block::new(color, *x, y - below)
+                    })
+                    .collect();
+                for (_, y) in cells.iter() {
+                    filled[*y as usize] += 1;
+                    if filled[*y as usize] == 10 {
+                        cleared.push(*y);
+                    }
+                }
+                (*kind, blocks)
+            })
+            .collect()
+    }
+
+    fn color(kind: TetrominoKind) -> Color {
+        match kind {
+            TetrominoKind::I => Color::Cyan,
+            TetrominoKind::J => Color::Blue,
+            TetrominoKind::L => Color::Orange,
+            TetrominoKind::O => Color::Yellow,
+            TetrominoKind::S => Color::Green,
+            TetrominoKind::T => Color::Purple,
+            TetrominoKind::Z => Color::Red,
+        }
+    }
+
+    fn parse_fumen(value: &str, number: usize) -> Result<Fumen, String> {
+        let fumen = Fumen::decode(value).map_err(|err| format!("line {}: {}", number, err))?;
+        if !fumen.pages().iter().any(|page| page.piece().is_some()) {
+            return Err(format!("line {}: fumen without pieces", number));
+        }
+        Ok(fumen)
+    }
+
+    fn parse_pieces(value: &str, number: usize) -> Result<Vec<TetrominoKind>, String> {
+        let pieces = value
+            .split_whitespace()
+            .map(|name| {
+                TetrominoKind::parse(name)
+                    .ok_or(format!("line {}: unknown piece `{}`", number, name))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if pieces.is_empty() {
+            return Err(format!("line {}: no pieces", number));
+        }
+        Ok(pieces)
+    }
+
+    // Steps are numbered 1-9 and then a-z.
+    fn parse_row(row: &str, number: usize) -> Result<Vec<Option<usize>>, String> {
+        if row.chars().count() != 10 {
+            return Err(format!("line {}: a row must be 10 cells wide", number));
+        }
+        row.chars()
+            .map(|c| match (c, c.to_digit(36)) {
+                ('.', _) => Ok(None),
+                (_, Some(step)) if step > 0 && !c.is_ascii_uppercase() => {
+                    Ok(Some(step as usize - 1))
+                }
+                _ => Err(format!("line {}: unknown cell `{}`", number, c)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        fumen::Page,
+        move_generator::{MoveGenerator, Placement},
+        rules::Rules,
+    };
+
+    #[test]
+    fn test_parse_pack() {
+        let text = "# comment\n\nname: A\npieces: I O\nfield:\n22........\n221111....\n\nname: B\npieces: T\nfield:\n1.........\n11........\n1.........\n";
+        let drills = Drill::parse_pack(text).unwrap();
+        assert_eq!(2, drills.len());
+        assert_eq!("A", drills[0].title());
+        assert_eq!(
+            vec![
+                Block::new(Color::Cyan, 2, 0),
+                Block::new(Color::Cyan, 3, 0),
+                Block::new(Color::Cyan, 4, 0),
+                Block::new(Color::Cyan, 5, 0),
+            ],
+            drills[0].target()
+        );
+        assert_eq!(TetrominoKind::O, drills[0].targets[1].0);
+        assert_eq!(4, drills[1].target().len());
+    }
+
+    #[test]
+    fn test_parse_pack_line_clear() {
+        let text = "name: A\npieces: I I O I\nfield:\n4444....33\n1111222233\n";
+        let drill = &Drill::parse_pack(text).unwrap()[0];
+        let bottom = |blocks: &[Block]| blocks.iter().map(|block| *block.y()).min().unwrap();
+        assert_eq!(0, bottom(&drill.targets[2].1));
+        assert_eq!(0, bottom(&drill.targets[3].1));
+    }
+
+    #[test]
+    fn test_parse_pack_error() {
+        assert!(Drill::parse_pack("name: A\npieces: O\nfield:\n111.......\n11........\n").is_err());
+        assert!(Drill::parse_pack("name: A\npieces: O\nfield:\nQQ........\n").is_err());
+        assert!(Drill::parse_pack("name: A\npieces: O\nfield:\n11\n11\n").is_err());
+        assert!(Drill::parse_pack("name: A\npieces: O\nfield:\n22........\n22........\n").is_err());
+        assert!(Drill::parse_pack("name: A\npieces: Q\n").is_err());
+        assert!(Drill::parse_pack("name: A\nfield:\n1111......\n").is_err());
+        assert!(Drill::parse_pack("pieces: I\nfield:\n1111......\n").is_err());
+        assert!(Drill::parse_pack("name A\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_pack_fumen() {
+        let drill = build_drill();
+        let mut field = drill.initial_field();
+        let mut pages = Vec::new();
+        for kind in [TetrominoKind::I, TetrominoKind::O].iter() {
+            let tetromino = build_tetromino(&drill, *kind);
+            let placement =
+                Placement::new(*kind, tetromino.direction(), tetromino.blocks(), Vec::new());
+            pages.push(Page::new(
+                field.clone(),
+                Some(placement.clone()),
+                String::new(),
+            ));
+            field = placement.apply(&field).0;
+        }
+        let text = format!("name: B\nfumen: {}\n", Fumen::new(pages).encode());
+        let fumen = &Drill::parse_pack(&text).unwrap()[0];
+        assert_eq!(2, fumen.targets.len());
+        for ((kind, blocks), (target_kind, target)) in
+            fumen.targets.iter().zip(drill.targets.iter())
+        {
+            assert_eq!(target_kind, kind);
+            assert!(Drill::is_same_cells(target, blocks));
+        }
+
+        let empty = Fumen::new(vec![Page::new(field, None, String::new())]).encode();
+        assert!(Drill::parse_pack(&format!("name: B\nfumen: {}\n", empty)).is_err());
+        assert!(Drill::parse_pack(&format!("name: B\npieces: I\n{}", &text[8..])).is_err());
+        assert!(Drill::parse_pack("name: B\nfumen: v115@\n").is_err());
+    }
+
+    #[test]
+    fn test_on_lock() {
+        let mut drill = build_drill();
+        let mut field = drill.initial_field();
+        let stats = Stats::new();
+
+        let mut tetromino = build_tetromino(&drill, TetrominoKind::I);
+        drill.on_lock(&mut field, tetromino.as_ref(), &stats);
+        assert!(!drill.take_restart());
+        assert_eq!(TetrominoKind::O, drill.targets[drill.placed].0);
+
+        tetromino.shift(0, 1);
+        drill.on_lock(&mut field, tetromino.as_ref(), &stats);
+        assert!(drill.take_restart());
+        assert!(!drill.take_restart());
+        assert_eq!(drill.targets[0].1, drill.target());
+        assert_eq!(vec!["A", "CLEARED", "0/1"], drill.status(&stats));
+    }
+
+    #[test]
+    fn test_on_lock_order() {
+        let mut drill = build_drill();
+        let mut field = drill.initial_field();
+        let stats = Stats::new();
+        let tetromino = build_tetromino(&drill, TetrominoKind::O);
+        drill.on_lock(&mut field, tetromino.as_ref(), &stats);
+        assert!(drill.take_restart());
+    }
+
+    #[test]
+    fn test_on_lock_completed() {
+        let mut drill = build_drill();
+        let mut field = drill.initial_field();
+        let stats = Stats::new();
+        for kind in [TetrominoKind::I, TetrominoKind::O].iter() {
+            let tetromino = build_tetromino(&drill, *kind);
+            drill.on_lock(&mut field, tetromino.as_ref(), &stats);
+        }
+        assert!(drill.take_restart());
+        assert_eq!(vec!["A", "CLEARED 1/1"], drill.result(&stats));
+    }
+
+    // Every bundled opener must be buildable: each step is reachable once the previous ones are placed.
+    #[test]
+    fn test_bundled_pack() {
+        let drills = Drill::parse_pack(include_str!("../../assets/openers.txt")).unwrap();
+        assert!(!drills.is_empty());
+        let generator = MoveGenerator::new(Rules::modern());
+        for drill in drills.iter() {
+            let mut field = drill.initial_field();
+            for (step, (kind, blocks)) in drill.targets.iter().enumerate() {
+                let tetromino = Rules::modern().build_tetromino(*kind);
+                let placement = generator
+                    .generate(&field, tetromino.as_ref())
+                    .into_iter()
+                    .find(|placement| Drill::is_same_cells(blocks, placement.blocks()));
+                match placement {
+                    Some(placement) => field = placement.apply(&field).0,
+                    None => panic!("{}: step {} cannot be placed", drill.title(), step + 1),
+                }
+            }
+        }
+    }
+
+    fn build_tetromino(drill: &Drill, kind: TetrominoKind) -> Box<dyn Tetromino> {
+        let left = |blocks: &[Block]| blocks.iter().map(|block| *block.x()).min().unwrap();
+        let bottom = |blocks: &[Block]| blocks.iter().map(|block| *block.y()).min().unwrap();
+        let mut tetromino = Rules::modern().build_tetromino(kind);
+        let (_, target) = drill
+            .targets
+            .iter()
+            .find(|(target, _)| *target == kind)
+            .unwrap();
+        let blocks = tetromino.blocks();
+        tetromino.shift(
+            left(target) - left(&blocks),
+            bottom(target) - bottom(&blocks),
+        );
+        tetromino
+    }
+
+    fn build_drill() -> Drill {
+        Drill::parse_pack("name: A\npieces: I O\nfield:\n22........\n221111....\n").unwrap()[0]
+            .clone()
+    }
+}
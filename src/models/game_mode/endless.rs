@@ -0,0 +1,35 @@
+use crate::models::{
+    field::Field,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    layout::Layout,
+    stats::Stats,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Endless;
+
+impl GameMode for Endless {
+    fn name(&self) -> &str {
+        "endless"
+    }
+    fn layout(&self) -> Layout {
+        Layout::standard()
+    }
+    fn gravity(&self, _stats: &Stats) -> Gravity {
+        Gravity::frames_per_row(16)
+    }
+    fn is_finished(&self, _field: &Field, _stats: &Stats) -> bool {
+        false
+    }
+    fn record(&self, stats: &Stats) -> Option<Record> {
+        Some(Record::Score(*stats.score()))
+    }
+    fn result(&self, stats: &Stats) -> Vec<String> {
+        vec![
+            "GAME OVER".to_string(),
+            format!("SCORE {}", stats.score()),
+            format!("LINES {}", stats.lines()),
+        ]
+    }
+}
@@ -0,0 +1,108 @@
+use crate::models::{
+    field::Field,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    layout::{HudItem, Layout},
+    stats::Stats,
+};
+
+const GOAL: i32 = 150;
+const MAX_LEVEL: i32 = 15;
+const GRAVITY: [i32; 15] = [60, 48, 37, 28, 21, 16, 11, 8, 6, 4, 3, 2, 1, 1, 1];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Marathon {
+    personal_best: Option<i32>,
+}
+
+impl GameMode for Marathon {
+    fn name(&self) -> &str {
+        "marathon"
+    }
+    fn layout(&self) -> Layout {
+        Layout::new(vec![
+            HudItem::Hold,
+            HudItem::Next,
+            HudItem::Score,
+            HudItem::CappedLevel(MAX_LEVEL),
+            HudItem::Goal(GOAL),
+            HudItem::Time,
+            HudItem::Action,
+        ])
+    }
+    fn gravity(&self, stats: &Stats) -> Gravity {
+        Gravity::frames_per_row(GRAVITY[(stats.level().min(MAX_LEVEL) - 1) as usize])
+    }
+    fn is_finished(&self, _field: &Field, stats: &Stats) -> bool {
+        *stats.lines() >= GOAL
+    }
+    fn record(&self, stats: &Stats) -> Option<Record> {
+        Some(Record::Score(*stats.score()))
+    }
+    fn result(&self, stats: &Stats) -> Vec<String> {
+        let score = *stats.score();
+        vec![
+            if *stats.lines() >= GOAL {
+                "CONGRATULATIONS".to_string()
+            } else {
+                "GAME OVER".to_string()
+            },
+            format!("SCORE {}", score),
+            format!("LEVEL {}", stats.level().min(MAX_LEVEL)),
+            format!("LINES {}", stats.lines()),
+            match self.personal_best {
+                Some(best) if score <= best => format!("PB {}", best),
+                _ => "NEW PB".to_string(),
+            },
+        ]
+    }
+}
+
+impl Marathon {
+    pub fn new(personal_best: Option<i32>) -> Self {
+        Self { personal_best }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{clear::Clear, combo::Combo};
+
+    #[test]
+    fn test_gravity() {
+        let marathon = Marathon::new(None);
+        let mut stats = Stats::new();
+        assert_eq!(Gravity::frames_per_row(60), marathon.gravity(&stats));
+        let mut combo = Combo::new();
+        for _ in 0..35 {
+            stats.add_clear(combo.update(Clear::new(4, false), false));
+        }
+        assert_eq!(15, stats.level());
+        assert_eq!(Gravity::frames_per_row(1), marathon.gravity(&stats));
+        for _ in 0..3 {
+            stats.add_clear(combo.update(Clear::new(4, false), false));
+        }
+        assert_eq!(16, stats.level());
+        assert_eq!(Gravity::frames_per_row(1), marathon.gravity(&stats));
+    }
+
+    #[test]
+    fn test_is_finished() {
+        let marathon = Marathon::new(None);
+        let mut stats = Stats::new();
+        let mut combo = Combo::new();
+        for _ in 0..37 {
+            stats.add_clear(combo.update(Clear::new(4, false), false));
+        }
+        assert!(!marathon.is_finished(&build_field(), &stats));
+        stats.add_clear(combo.update(Clear::new(2, false), false));
+        assert!(marathon.is_finished(&build_field(), &stats));
+        assert_eq!("CONGRATULATIONS", marathon.result(&stats)[0]);
+        assert_eq!("LEVEL 15", marathon.result(&stats)[2]);
+    }
+
+    fn build_field() -> Field {
+        Field::new(vec![vec![None; 10]; 24])
+    }
+}
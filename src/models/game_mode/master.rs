@@ -0,0 +1,290 @@
+use crate::models::{
+    combo::ClearEvent,
+    delay::Delay,
+    field::Field,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    hud::Hud,
+    layout::{HudItem, Layout},
+    rules::Rules,
+    stats::Stats,
+};
+
+const MAX_LEVEL: i32 = 999;
+const SECTION: i32 = 100;
+const GRAVITY: [(i32, i32); 30] = [
+    (0, 4),
+    (30, 6),
+    (35, 8),
+    (40, 10),
+    (50, 12),
+    (60, 16),
+    (70, 32),
+    (80, 48),
+    (90, 64),
+    (100, 80),
+    (120, 96),
+    (140, 112),
+    (160, 128),
+    (170, 144),
+    (200, 4),
+    (220, 32),
+    (230, 64),
+    (233, 96),
+    (236, 128),
+    (239, 160),
+    (243, 192),
+    (247, 224),
+    (251, 256),
+    (300, 512),
+    (330, 768),
+    (360, 1024),
+    (400, 1280),
+    (420, 1024),
+    (450, 768),
+    (500, 5120),
+];
+const GRADES: [(&str, i32); 18] = [
+    ("9", 0),
+    ("8", 400),
+    ("7", 800),
+    ("6", 1400),
+    ("5", 2000),
+    ("4", 3500),
+    ("3", 5500),
+    ("2", 8000),
+    ("1", 12000),
+    ("S1", 16000),
+    ("S2", 22000),
+    ("S3", 30000),
+    ("S4", 40000),
+    ("S5", 52000),
+    ("S6", 66000),
+    ("S7", 82000),
+    ("S8", 100000),
+    ("S9", 120000),
+];
+const GM_SCORE: i32 = 126000;
+// Level, time limit in milliseconds and score required on the way to the GM grade.
+const GM_CHECKPOINTS: [(i32, i32, i32); 3] = [
+    (300, 255000, 12000),
+    (500, 450000, 40000),
+    (999, 810000, 120000),
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Master {
+    level: i32,
+    score: i32,
+    combo: i32,
+    section_times: Vec<i32>,
+    gm_qualified: bool,
+    personal_best: Option<i32>,
+}
+
+impl GameMode for Master {
+    fn name(&self) -> &str {
+        "master"
+    }
+    fn layout(&self) -> Layout {
+        Layout::new(vec![HudItem::Next, HudItem::Status, HudItem::Time])
+    }
+    fn rules(&self) -> Rules {
+        Rules::master()
+    }
+    fn gravity(&self, _stats: &Stats) -> Gravity {
+        let (_, rows) = GRAVITY
+            .iter()
+            .rev()
+            .find(|(level, _)| *level <= self.level)
+            .unwrap();
+        Gravity::new(*rows, 256)
+    }
+    fn delay(&self, _stats: &Stats) -> Delay {
+        match self.level / SECTION {
+            0..=4 => Delay::new(25, 40, 30),
+            5 => Delay::new(25, 25, 30),
+            6 => Delay::new(25, 16, 30),
+            7 => Delay::new(16, 12, 30),
+            8 => Delay::new(12, 6, 30),
+            _ => Delay::new(12, 6, 17),
+        }
+    }
+    fn on_spawn(&mut self, _stats: &Stats) {
+        if self.level % SECTION != SECTION - 1 && self.level != MAX_LEVEL - 1 {
+            self.level += 1;
+        }
+    }
+    fn on_line_clear(&mut self, event: &ClearEvent, stats: &Stats) {
+        let lines = *event.lines();
+        if *event.combo() == 0 {
+            self.combo = 1;
+        }
+        self.combo += 2 * lines - 2;
+        let bravo = if *event.all_clear() { 4 } else { 1 };
+        self.score += (self.level + lines + 3) / 4 * lines * self.combo * bravo;
+
+        let previous = self.level;
+        self.level = (self.level + lines).min(MAX_LEVEL);
+        for (level, millis, score) in GM_CHECKPOINTS.iter() {
+            if previous < *level && self.level >= *level {
+                self.gm_qualified &= stats.elapsed_millis() <= *millis && self.score >= *score;
+            }
+        }
+        while (self.section_times.len() as i32 + 1) * SECTION <= self.level
+            || (self.level == MAX_LEVEL && self.section_times.len() < 10)
+        {
+            let elapsed: i32 = self.section_times.iter().sum();
+            self.section_times.push(stats.elapsed_millis() - elapsed);
+        }
+    }
+    fn is_finished(&self, _field: &Field, _stats: &Stats) -> bool {
+        self.level >= MAX_LEVEL
+    }
+    fn record(&self, _stats: &Stats) -> Option<Record> {
+        Some(Record::Score(self.grade_index() as i32))
+    }
+    fn result(&self, stats: &Stats) -> Vec<String> {
+        let mut result = vec![
+            if self.level >= MAX_LEVEL {
+                "COMPLETED".to_string()
+            } else {
+                "GAME OVER".to_string()
+            },
+            format!("GRADE {}", self.grade()),
+            format!("LEVEL {}", self.level),
+            format!("TIME {}", Hud::format_millis(stats.elapsed_millis())),
+        ];
+        for (idx, time) in self.section_times.iter().enumerate() {
+            result.push(format!(
+                "{:>3} {}",
+                idx as i32 * SECTION,
+                Hud::format_millis(*time)
+            ));
+        }
+        result.push(match self.personal_best {
+            Some(best) if self.grade_index() as i32 <= best => {
+                format!("PB {}", Self::grade_name(best as usize))
+            }
+            _ => "NEW PB".to_string(),
+        });
+        result
+    }
+    fn status(&self, _stats: &Stats) -> Vec<String> {
+        let next = ((self.level / SECTION + 1) * SECTION).min(MAX_LEVEL);
+        vec![
+            "LEVEL".to_string(),
+            format!("{:>3}/{}", self.level, next),
+            "GRADE".to_string(),
+            self.grade().to_string(),
+        ]
+    }
+}
+
+impl Master {
+    pub fn new(personal_best: Option<i32>) -> Self {
+        Self {
+            level: 0,
+            score: 0,
+            combo: 1,
+            section_times: Vec::new(),
+            gm_qualified: true,
+            personal_best,
+        }
+    }
+
+    pub fn level(&self) -> &i32 {
+        &self.level
+    }
+
+    pub fn score(&self) -> &i32 {
+        &self.score
+    }
+
+    pub fn section_times(&self) -> &Vec<i32> {
+        &self.section_times
+    }
+
+    pub fn grade(&self) -> &str {
+        Self::grade_name(self.grade_index())
+    }
+}
+
+impl Master {
+    fn grade_index(&self) -> usize {
+        if self.level >= MAX_LEVEL && self.gm_qualified && self.score >= GM_SCORE {
+            return GRADES.len();
+        }
+        GRADES
+            .iter()
+            .rposition(|(_, score)| *score <= self.score)
+            .unwrap()
+    }
+
+    fn grade_name(idx: usize) -> &'static str {
+        GRADES.get(idx).map(|(name, _)| *name).unwrap_or("GM")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{clear::Clear, combo::Combo};
+
+    #[test]
+    fn test_on_spawn() {
+        let mut master = Master::new(None);
+        let stats = Stats::new();
+        for _ in 0..120 {
+            master.on_spawn(&stats);
+        }
+        assert_eq!(99, *master.level());
+        let mut combo = Combo::new();
+        master.on_line_clear(&combo.update(Clear::new(1, false), false), &stats);
+        assert_eq!(100, *master.level());
+        master.on_spawn(&stats);
+        assert_eq!(101, *master.level());
+        assert_eq!(1, master.section_times().len());
+    }
+
+    #[test]
+    fn test_gravity() {
+        let mut master = Master::new(None);
+        let stats = Stats::new();
+        assert_eq!(Gravity::new(4, 256), master.gravity(&stats));
+        assert_eq!(Delay::new(25, 40, 30), master.delay(&stats));
+        let mut combo = Combo::new();
+        for _ in 0..125 {
+            master.on_line_clear(&combo.update(Clear::new(4, false), false), &stats);
+        }
+        assert_eq!(500, *master.level());
+        assert!(master.gravity(&stats).is_instant());
+        assert_eq!(Delay::new(25, 25, 30), master.delay(&stats));
+        assert_eq!(5, master.section_times().len());
+    }
+
+    #[test]
+    fn test_on_line_clear() {
+        let mut master = Master::new(None);
+        let stats = Stats::new();
+        let mut combo = Combo::new();
+        master.on_line_clear(&combo.update(Clear::new(4, false), false), &stats);
+        assert_eq!(28, *master.score());
+        master.on_line_clear(&combo.update(Clear::new(1, false), false), &stats);
+        assert_eq!(28 + 14, *master.score());
+        assert_eq!("9", master.grade());
+    }
+
+    #[test]
+    fn test_grade() {
+        let mut master = Master::new(None);
+        master.score = 16000;
+        assert_eq!("S1", master.grade());
+        master.score = 130000;
+        master.level = MAX_LEVEL;
+        assert_eq!("GM", master.grade());
+        master.gm_qualified = false;
+        assert_eq!("S9", master.grade());
+        assert_eq!(Some(Record::Score(17)), master.record(&Stats::new()));
+    }
+}
@@ -0,0 +1,320 @@
+use crate::models::{
+    block::Color,
+    combo::ClearEvent,
+    field::Field,
+    fumen::Fumen,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    layout::{HudItem, Layout},
+    stats::Stats,
+    tetromino::TetrominoKind,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Goal {
+    Lines(i32),
+    PerfectClear,
+    TSpinDouble,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Puzzle {
+    name: String,
+    field: Field,
+    queue: Vec<TetrominoKind>,
+    goal: Goal,
+    solved: bool,
+}
+
+impl GameMode for Puzzle {
+    fn name(&self) -> &str {
+        "puzzle"
+    }
+    fn layout(&self) -> Layout {
+        Layout::new(vec![HudItem::Hold, HudItem::Next, HudItem::Status])
+    }
+    fn initial_field(&self) -> Field {
+        self.field.clone()
+    }
+    fn queue(&self) -> Option<Vec<TetrominoKind>> {
+        Some(self.queue.clone())
+    }
+    fn gravity(&self, _stats: &Stats) -> Gravity {
+        Gravity::frames_per_row(60)
+    }
+    fn on_line_clear(&mut self, event: &ClearEvent, stats: &Stats) {
+        self.solved |= match self.goal {
+            Goal::Lines(lines) => *stats.lines() >= lines,
+            Goal::PerfectClear => *event.all_clear(),
+            Goal::TSpinDouble => *event.t_spin() && *event.lines() == 2,
+        };
+    }
+    fn is_finished(&self, _field: &Field, stats: &Stats) -> bool {
+        self.solved || *stats.pieces() >= self.queue.len() as i32
+    }
+    fn record(&self, _stats: &Stats) -> Option<Record> {
+        None
+    }
+    fn result(&self, _stats: &Stats) -> Vec<String> {
+        vec![
+            if self.solved {
+                "SOLVED".to_string()
+            } else {
+                "FAILED".to_string()
+            },
+            self.name.clone(),
+        ]
+    }
+    fn status(&self, stats: &Stats) -> Vec<String> {
+        vec![
+            "GOAL".to_string(),
+            match self.goal {
+                Goal::Lines(lines) => format!("{} LINES", lines),
+                Goal::PerfectClear => "ALL CLEAR".to_string(),
+                Goal::TSpinDouble => "T-SPIN DOUBLE".to_string(),
+            },
+            "PIECES LEFT".to_string(),
+            (self.queue.len() as i32 - stats.pieces())
+                .max(0)
+                .to_string(),
+        ]
+    }
+}
+
+impl Puzzle {
+    pub fn new(name: String, field: Field, queue: Vec<TetrominoKind>, goal: Goal) -> Self {
+        Self {
+            name,
+            field,
+            queue,
+            goal,
+            solved: false,
+        }
+    }
+
+    pub fn parse_pack(text: &str) -> Result<Vec<Self>, String> {
+        let mut puzzles = Vec::new();
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim()))
+            .filter(|(_, line)| !line.starts_with('#'))
+            .peekable();
+
+        while lines.peek().is_some() {
+            let mut name = None;
+            let mut goal = None;
+            let mut queue = None;
+            let mut rows = Vec::new();
+            let mut fumen = None;
+            while let Some((number, line)) = lines.next() {
+                if line.is_empty() {
+                    break;
+                }
+                let (key, value) = match line.find(':') {
+                    Some(idx) => (&line[..idx], line[idx + 1..].trim()),
+                    None => return Err(format!("line {}: expected `key: value`", number)),
+                };
+                match key {
+                    "name" => name = Some(value.to_string()),
+                    "goal" => goal = Some(Self::parse_goal(value, number)?),
+                    "queue" => queue = Some(Self::parse_queue(value, number)?),
+                    "fumen" => fumen = Some(Self::parse_fumen(value, number)?),
+                    "field" => {
+                        while let Some((number, row)) = lines.next_if(|(_, line)| !line.is_empty())
+                        {
+                            rows.push(Self::parse_row(row, number)?);
+                        }
+                    }
+                    _ => return Err(format!("line {}: unknown key `{}`", number, key)),
+                }
+            }
+
+            if name.is_none()
+                && goal.is_none()
+                && queue.is_none()
+                && rows.is_empty()
+                && fumen.is_none()
+            {
+                continue;
+            }
+            let name = name.ok_or("puzzle without a name")?;
+            let goal = goal.ok_or(format!("{}: missing goal", name))?;
+            let queue = queue.ok_or(format!("{}: missing queue", name))?;
+            if rows.len() > 20 {
+                return Err(format!("{}: field is higher than 20 rows", name));
+            }
+            let field = match fumen {
+                Some(_) if !rows.is_empty() => {
+                    return Err(format!("{}: field and fumen are both given", name));
+                }
+                Some(field) if field.blocks().iter().any(|block| *block.y() >= 20) => {
+                    return Err(format!("{}: field is higher than 20 rows", name));
+                }
+                Some(field) => field,
+                None => {
+                    rows.reverse();
+                    rows.resize(24, vec![None; 10]);
+                    Field::new(rows)
+                }
+            };
+            puzzles.push(Self::new(name, field, queue, goal));
+        }
+        Ok(puzzles)
+    }
+
+    pub fn title(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.solved
+    }
+}
+
+impl Puzzle {
+    fn parse_goal(value: &str, number: usize) -> Result<Goal, String> {
+        let words: Vec<_> = value.split_whitespace().collect();
+        match words.as_slice() {
+            ["lines", lines] => lines
+                .parse()
+                .map(Goal::Lines)
+                .map_err(|_| format!("line {}: invalid line count `{}`", number, lines)),
+            ["perfect"] => Ok(Goal::PerfectClear),
+            ["tsd"] => Ok(Goal::TSpinDouble),
+            _ => Err(format!("line {}: unknown goal `{}`", number, value)),
+        }
+    }
+
+    fn parse_queue(value: &str, number: usize) -> Result<Vec<TetrominoKind>, String> {
+        let queue = value
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c {
+                'I' => Ok(TetrominoKind::I),
+                'J' => Ok(TetrominoKind::J),
+                'L' => Ok(TetrominoKind::L),
+                'O' => Ok(TetrominoKind::O),
+                'S' => Ok(TetrominoKind::S),
+                'T' => Ok(TetrominoKind::T),
+                'Z' => Ok(TetrominoKind::Z),
+                _ => Err(format!("line {}: unknown piece `{}`", number, c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if queue.is_empty() {
+            return Err(format!("line {}: empty queue", number));
+        }
+        Ok(queue)
+    }
+
+    fn parse_fumen(value: &str, number: usize) -> Result<Field, String> {
+        let fumen = Fumen::decode(value).map_err(|err| format!("line {}: {}", number, err))?;
+        match fumen.pages().first() {
+            Some(page) => Ok(page.field().clone()),
+            None => Err(format!("line {}: fumen without pages", number)),
+        }
+    }
+
+    fn parse_row(row: &str, number: usize) -> Result<Vec<Option<Color>>, String> {
+        if row.chars().count() != 10 {
+            return Err(format!("line {}: a row must be 10 cells wide", number));
+        }
+        row.chars()
+            .map(|c| match c {
+                '.' => Ok(None),
+                'X' | 'G' => Ok(Some(Color::Grey)),
+                'I' => Ok(Some(Color::Cyan)),
+                'J' => Ok(Some(Color::Blue)),
+                'L' => Ok(Some(Color::Orange)),
+                'O' => Ok(Some(Color::Yellow)),
+                'S' => Ok(Some(Color::Green)),
+                'T' => Ok(Some(Color::Purple)),
+                'Z' => Ok(Some(Color::Red)),
+                _ => Err(format!("line {}: unknown cell `{}`", number, c)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{block::Block, clear::Clear, combo::Combo, fumen::Page};
+
+    #[test]
+    fn test_parse_pack() {
+        let text = "# comment\n\nname: A\ngoal: lines 2\nqueue: O I\nfield:\nXXXXXXXX..\nTXXXXXXX..\n\nname: B\ngoal: tsd\nqueue: T\n";
+        let puzzles = Puzzle::parse_pack(text).unwrap();
+        assert_eq!(2, puzzles.len());
+        assert_eq!("A", puzzles[0].title());
+        assert_eq!(Goal::Lines(2), puzzles[0].goal);
+        assert_eq!(
+            Some(vec![TetrominoKind::O, TetrominoKind::I]),
+            puzzles[0].queue()
+        );
+        let blocks = puzzles[0].initial_field().blocks();
+        assert_eq!(16, blocks.len());
+        assert!(blocks.contains(&Block::new(Color::Purple, 0, 0)));
+        assert!(blocks.contains(&Block::new(Color::Grey, 7, 1)));
+        assert_eq!(Goal::TSpinDouble, puzzles[1].goal);
+        assert!(puzzles[1].initial_field().blocks().is_empty());
+    }
+
+    #[test]
+    fn test_parse_pack_error() {
+        assert!(Puzzle::parse_pack("name: A\ngoal: lines\nqueue: T\n").is_err());
+        assert!(Puzzle::parse_pack("name: A\ngoal: tsd\nqueue: T Q\n").is_err());
+        assert!(Puzzle::parse_pack("name: A\ngoal: tsd\nqueue: T\nfield:\nXX\n").is_err());
+        assert!(Puzzle::parse_pack("name: A\nqueue: T\n").is_err());
+        assert!(Puzzle::parse_pack("name A\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_pack_fumen() {
+        let text = "name: A\ngoal: lines 2\nqueue: O\nfield:\nXXXXXXXX..\nXXXXXXXX..\n";
+        let field = Puzzle::parse_pack(text).unwrap()[0].initial_field();
+        let fumen = Fumen::new(vec![Page::new(field.clone(), None, String::new())]).encode();
+        let text = format!("name: A\ngoal: lines 2\nqueue: O\nfumen: {}\n", fumen);
+        assert_eq!(field, Puzzle::parse_pack(&text).unwrap()[0].initial_field());
+        assert!(Puzzle::parse_pack("name: A\ngoal: tsd\nqueue: T\nfumen: v115@\n").is_err());
+
+        let both = format!("{}field:\nXXXXXXXX..\n", text);
+        assert!(Puzzle::parse_pack(&both).is_err());
+        let mut rows = vec![vec![None; 10]; 24];
+        rows[20][0] = Some(Color::Grey);
+        let fumen = Fumen::new(vec![Page::new(Field::new(rows), None, String::new())]).encode();
+        let text = format!("name: A\ngoal: lines 2\nqueue: O\nfumen: {}\n", fumen);
+        assert!(Puzzle::parse_pack(&text).is_err());
+    }
+
+    #[test]
+    fn test_bundled_pack() {
+        let puzzles = Puzzle::parse_pack(include_str!("../../assets/puzzles.txt")).unwrap();
+        assert!(!puzzles.is_empty());
+    }
+
+    #[test]
+    fn test_is_finished() {
+        let mut puzzle = Puzzle::new(
+            "A".to_string(),
+            Field::new(vec![vec![None; 10]; 24]),
+            vec![TetrominoKind::T, TetrominoKind::I],
+            Goal::TSpinDouble,
+        );
+        let field = puzzle.initial_field();
+        let mut stats = Stats::new();
+        let mut combo = Combo::new();
+
+        let event = combo.update(Clear::new(2, false), false);
+        stats.add_clear(event);
+        puzzle.on_line_clear(&event, &stats);
+        assert!(!puzzle.is_finished(&field, &stats));
+
+        let event = combo.update(Clear::new(2, false), true);
+        stats.add_clear(event);
+        puzzle.on_line_clear(&event, &stats);
+        assert!(puzzle.is_solved());
+        assert!(puzzle.is_finished(&field, &stats));
+        assert_eq!("SOLVED", puzzle.result(&stats)[0]);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::models::{
+    field::Field,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    hud::Hud,
+    layout::{HudItem, Layout},
+    stats::Stats,
+    visibility::Visibility,
+};
+
+const DURATION: i32 = 3300;
+const FADE: i32 = 300;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Roll {
+    visibility: Visibility,
+    personal_best: Option<i32>,
+}
+
+impl GameMode for Roll {
+    fn name(&self) -> &str {
+        match self.visibility {
+            Visibility::Invisible => "invisible",
+            _ => "fading",
+        }
+    }
+    fn layout(&self) -> Layout {
+        Layout::new(vec![
+            HudItem::Hold,
+            HudItem::Next,
+            HudItem::Lines,
+            HudItem::Time,
+            HudItem::Action,
+        ])
+    }
+    fn gravity(&self, _stats: &Stats) -> Gravity {
+        Gravity::frames_per_row(10)
+    }
+    fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+    fn is_finished(&self, _field: &Field, stats: &Stats) -> bool {
+        *stats.frames() >= DURATION
+    }
+    fn record(&self, stats: &Stats) -> Option<Record> {
+        if *stats.frames() >= DURATION {
+            Some(Record::Score(*stats.lines()))
+        } else {
+            None
+        }
+    }
+    fn result(&self, stats: &Stats) -> Vec<String> {
+        let lines = *stats.lines();
+        vec![
+            if *stats.frames() >= DURATION {
+                "ROLL CLEARED".to_string()
+            } else {
+                "GAME OVER".to_string()
+            },
+            format!("LINES {}", lines),
+            format!("TIME {}", Hud::format_millis(stats.elapsed_millis())),
+            match self.personal_best {
+                Some(best) if lines <= best => format!("PB {}", best),
+                _ => "NEW PB".to_string(),
+            },
+        ]
+    }
+}
+
+impl Roll {
+    pub fn fading(personal_best: Option<i32>) -> Self {
+        Self {
+            visibility: Visibility::Fading(FADE),
+            personal_best,
+        }
+    }
+
+    pub fn invisible(personal_best: Option<i32>) -> Self {
+        Self {
+            visibility: Visibility::Invisible,
+            personal_best,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_finished() {
+        let roll = Roll::invisible(None);
+        let field = Field::new(vec![vec![None; 10]; 24]);
+        let mut stats = Stats::new();
+        for _ in 0..DURATION - 1 {
+            stats.tick();
+        }
+        assert!(!roll.is_finished(&field, &stats));
+        assert_eq!(None, roll.record(&stats));
+        stats.tick();
+        assert!(roll.is_finished(&field, &stats));
+        assert_eq!(Some(Record::Score(0)), roll.record(&stats));
+        assert_eq!("ROLL CLEARED", roll.result(&stats)[0]);
+    }
+
+    #[test]
+    fn test_visibility() {
+        assert_eq!("fading", Roll::fading(None).name());
+        assert_eq!(Visibility::Fading(FADE), Roll::fading(None).visibility());
+        assert_eq!("invisible", Roll::invisible(None).name());
+    }
+}
@@ -0,0 +1,212 @@
+use crate::models::{
+    combo::ClearEvent,
+    field::Field,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    hud::Hud,
+    layout::{HudItem, Layout},
+    stats::Stats,
+};
+
+const GOAL: i32 = 40;
+const SPLIT: i32 = 10;
+
+#[derive(Clone, Debug)]
+pub struct Sprint {
+    splits: Vec<i32>,
+    personal_best: Option<i32>,
+    clock: Option<fn() -> f64>,
+    started: Option<f64>,
+    finished: Option<i32>,
+}
+
+impl GameMode for Sprint {
+    fn name(&self) -> &str {
+        "sprint"
+    }
+    fn layout(&self) -> Layout {
+        Layout::new(vec![
+            HudItem::Hold,
+            HudItem::Next,
+            HudItem::Goal(GOAL),
+            HudItem::Status,
+            HudItem::Pps,
+            HudItem::Finesse,
+            HudItem::Action,
+        ])
+    }
+    fn gravity(&self, _stats: &Stats) -> Gravity {
+        Gravity::frames_per_row(60)
+    }
+    fn on_spawn(&mut self, _stats: &Stats) {
+        if self.started.is_none() {
+            self.started = self.clock.map(|clock| clock());
+        }
+    }
+    fn on_line_clear(&mut self, _event: &ClearEvent, stats: &Stats) {
+        let elapsed = self.elapsed(stats);
+        while (self.splits.len() as i32 + 1) * SPLIT <= (*stats.lines()).min(GOAL) {
+            self.splits.push(elapsed);
+        }
+        if *stats.lines() >= GOAL && self.finished.is_none() {
+            self.finished = Some(elapsed);
+        }
+    }
+    fn is_finished(&self, _field: &Field, stats: &Stats) -> bool {
+        *stats.lines() >= GOAL
+    }
+    fn record(&self, stats: &Stats) -> Option<Record> {
+        if *stats.lines() >= GOAL {
+            Some(Record::Time(self.elapsed(stats)))
+        } else {
+            None
+        }
+    }
+    fn result(&self, stats: &Stats) -> Vec<String> {
+        let time = match self.record(stats) {
+            Some(record) => record.value(),
+            None => return vec!["FAILED".to_string(), format!("LINES {}", stats.lines())],
+        };
+
+        let mut result = vec![
+            "FINISHED".to_string(),
+            format!("TIME {}", Hud::format_millis(time)),
+        ];
+        for (idx, split) in self.splits.iter().enumerate() {
+            result.push(format!(
+                "{:>2} {}",
+                (idx as i32 + 1) * SPLIT,
+                Hud::format_millis(*split)
+            ));
+        }
+        result.push(match self.personal_best {
+            Some(best) if time >= best => format!("PB +{}", Hud::format_millis(time - best)),
+            Some(best) => format!("NEW PB -{}", Hud::format_millis(best - time)),
+            None => "NEW PB".to_string(),
+        });
+        result
+    }
+    fn status(&self, stats: &Stats) -> Vec<String> {
+        vec!["TIME".to_string(), Hud::format_millis(self.elapsed(stats))]
+    }
+}
+
+impl Sprint {
+    pub fn new(personal_best: Option<i32>) -> Self {
+        Self {
+            splits: Vec::new(),
+            personal_best,
+            clock: None,
+            started: None,
+            finished: None,
+        }
+    }
+
+    // Times the run with a wall clock in milliseconds instead of counting frames, whose rate
+    // depends on the display.
+    pub fn with_clock(personal_best: Option<i32>, clock: fn() -> f64) -> Self {
+        Self {
+            clock: Some(clock),
+            ..Self::new(personal_best)
+        }
+    }
+
+    pub fn splits(&self) -> &Vec<i32> {
+        &self.splits
+    }
+}
+
+impl Sprint {
+    fn elapsed(&self, stats: &Stats) -> i32 {
+        if let Some(finished) = self.finished {
+            return finished;
+        }
+        match (self.clock, self.started) {
+            (Some(clock), Some(started)) => (clock() - started) as i32,
+            _ => stats.elapsed_millis(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{clear::Clear, combo::Combo};
+    use std::cell::Cell;
+
+    thread_local! {
+        static NOW: Cell<f64> = const { Cell::new(0.0) };
+    }
+
+    #[test]
+    fn test_on_line_clear() {
+        let mut sprint = Sprint::new(None);
+        let stats = build_stats(9, 60);
+        sprint.on_line_clear(&build_event(), &stats);
+        assert_eq!(&Vec::<i32>::new(), sprint.splits());
+        let stats = build_stats(22, 60);
+        sprint.on_line_clear(&build_event(), &stats);
+        assert_eq!(&vec![22 * 1000, 22 * 1000], sprint.splits());
+        let stats = build_stats(44, 120);
+        sprint.on_line_clear(&build_event(), &stats);
+        assert_eq!(4, sprint.splits().len());
+        assert!(sprint.is_finished(&build_field(), &stats));
+    }
+
+    #[test]
+    fn test_result() {
+        let stats = build_stats(40, 60);
+        let mut sprint = Sprint::new(Some(39000));
+        sprint.on_line_clear(&build_event(), &stats);
+        let result = sprint.result(&stats);
+        assert_eq!("TIME 0:40.000", result[1]);
+        assert_eq!("40 0:40.000", result[5]);
+        assert_eq!("PB +0:01.000", result[6]);
+
+        let sprint = Sprint::new(Some(41000));
+        assert_eq!("NEW PB -0:01.000", sprint.result(&stats)[2]);
+
+        let stats = build_stats(39, 60);
+        assert_eq!(None, sprint.record(&stats));
+        assert_eq!("FAILED", sprint.result(&stats)[0]);
+    }
+
+    #[test]
+    fn test_clock() {
+        let mut sprint = Sprint::with_clock(None, now);
+        NOW.with(|now| now.set(1000.0));
+        sprint.on_spawn(&Stats::new());
+        NOW.with(|now| now.set(5000.0));
+        sprint.on_spawn(&Stats::new());
+        let stats = build_stats(40, 60);
+        assert_eq!(vec!["TIME", "0:04.000"], sprint.status(&stats));
+        sprint.on_line_clear(&build_event(), &stats);
+        NOW.with(|now| now.set(9000.0));
+        assert_eq!(&vec![4000; 4], sprint.splits());
+        assert_eq!(Some(Record::Time(4000)), sprint.record(&stats));
+    }
+
+    fn now() -> f64 {
+        NOW.with(|now| now.get())
+    }
+
+    fn build_stats(lines: i32, frames_per_line: i32) -> Stats {
+        let mut stats = Stats::new();
+        let mut combo = Combo::new();
+        for _ in 0..lines {
+            for _ in 0..frames_per_line {
+                stats.tick();
+            }
+            stats.add_clear(combo.update(Clear::new(1, false), false));
+        }
+        stats
+    }
+
+    fn build_field() -> Field {
+        Field::new(vec![vec![None; 10]; 24])
+    }
+
+    fn build_event() -> ClearEvent {
+        Combo::new().update(Clear::new(1, false), false)
+    }
+}
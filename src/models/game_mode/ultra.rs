@@ -0,0 +1,92 @@
+use crate::models::{
+    field::Field,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    layout::{HudItem, Layout},
+    stats::Stats,
+};
+
+const TIME_LIMIT: i32 = 120_000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ultra {
+    personal_best: Option<i32>,
+}
+
+impl GameMode for Ultra {
+    fn name(&self) -> &str {
+        "ultra"
+    }
+    fn layout(&self) -> Layout {
+        Layout::new(vec![
+            HudItem::Hold,
+            HudItem::Next,
+            HudItem::Score,
+            HudItem::Countdown(TIME_LIMIT),
+            HudItem::Lines,
+            HudItem::Pps,
+            HudItem::Action,
+        ])
+    }
+    fn gravity(&self, _stats: &Stats) -> Gravity {
+        Gravity::frames_per_row(60)
+    }
+    fn is_finished(&self, _field: &Field, stats: &Stats) -> bool {
+        stats.elapsed_millis() >= TIME_LIMIT
+    }
+    fn record(&self, stats: &Stats) -> Option<Record> {
+        Some(Record::Score(*stats.score()))
+    }
+    fn result(&self, stats: &Stats) -> Vec<String> {
+        let score = *stats.score();
+        vec![
+            if stats.elapsed_millis() >= TIME_LIMIT {
+                "TIME UP".to_string()
+            } else {
+                "GAME OVER".to_string()
+            },
+            format!("SCORE {}", score),
+            format!("LINES {}", stats.lines()),
+            match self.personal_best {
+                Some(best) if score <= best => format!("PB {}", best),
+                _ => "NEW PB".to_string(),
+            },
+        ]
+    }
+}
+
+impl Ultra {
+    pub fn new(personal_best: Option<i32>) -> Self {
+        Self { personal_best }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_finished() {
+        let ultra = Ultra::new(None);
+        let mut stats = Stats::new();
+        for _ in 0..7199 {
+            stats.tick();
+        }
+        assert!(!ultra.is_finished(&build_field(), &stats));
+        stats.tick();
+        assert!(ultra.is_finished(&build_field(), &stats));
+        assert_eq!("TIME UP", ultra.result(&stats)[0]);
+    }
+
+    #[test]
+    fn test_result() {
+        let stats = Stats::new();
+        assert_eq!("GAME OVER", Ultra::new(None).result(&stats)[0]);
+        assert_eq!("NEW PB", Ultra::new(None).result(&stats)[3]);
+        assert_eq!("PB 100", Ultra::new(Some(100)).result(&stats)[3]);
+    }
+
+    fn build_field() -> Field {
+        Field::new(vec![vec![None; 10]; 24])
+    }
+}
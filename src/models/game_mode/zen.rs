@@ -0,0 +1,39 @@
+use crate::models::{
+    field::Field,
+    game_mode::{GameMode, Record},
+    gravity::Gravity,
+    layout::{HudItem, Layout},
+    stats::Stats,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Zen;
+
+impl GameMode for Zen {
+    fn name(&self) -> &str {
+        "zen"
+    }
+    fn layout(&self) -> Layout {
+        Layout::new(vec![
+            HudItem::Hold,
+            HudItem::Next,
+            HudItem::Lines,
+            HudItem::Time,
+            HudItem::Pps,
+            HudItem::Finesse,
+            HudItem::Action,
+        ])
+    }
+    fn gravity(&self, _stats: &Stats) -> Gravity {
+        Gravity::frames_per_row(i32::MAX)
+    }
+    fn is_finished(&self, _field: &Field, _stats: &Stats) -> bool {
+        false
+    }
+    fn record(&self, _stats: &Stats) -> Option<Record> {
+        None
+    }
+    fn result(&self, stats: &Stats) -> Vec<String> {
+        vec!["GAME OVER".to_string(), format!("LINES {}", stats.lines())]
+    }
+}
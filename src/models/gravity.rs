@@ -0,0 +1,38 @@
+use derive_new::new;
+
+#[derive(Clone, Copy, Debug, Eq, new, PartialEq)]
+pub struct Gravity {
+    rows: i32,
+    frames: i32,
+}
+
+impl Gravity {
+    pub fn frames_per_row(frames: i32) -> Self {
+        Self::new(1, frames)
+    }
+
+    pub fn rows(&self) -> &i32 {
+        &self.rows
+    }
+
+    pub fn frames(&self) -> &i32 {
+        &self.frames
+    }
+
+    pub fn is_instant(&self) -> bool {
+        self.rows >= 20 * self.frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_instant() {
+        assert!(!Gravity::frames_per_row(1).is_instant());
+        assert!(!Gravity::new(5119, 256).is_instant());
+        assert!(Gravity::new(5120, 256).is_instant());
+        assert!(Gravity::new(20, 1).is_instant());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::models::{
+    ai::{evaluator::Weights, heuristic::Heuristic},
+    block::Block,
+    field::Field,
+    game::Game,
+    rules::Rules,
+    tetromino::TetrominoKind,
+};
+
+type Key = (
+    i32,
+    Field,
+    TetrominoKind,
+    Option<TetrominoKind>,
+    Vec<TetrominoKind>,
+);
+
+pub struct Hint {
+    heuristic: Heuristic,
+    key: Option<Key>,
+    blocks: Vec<Block>,
+}
+
+impl Hint {
+    pub fn new(rules: Rules, weights: Weights) -> Self {
+        Self {
+            heuristic: Heuristic::new(rules, weights),
+            key: None,
+            blocks: Vec::new(),
+        }
+    }
+
+    // Searches again only when the field, the piece, the hold or the queue has changed.
+    pub fn update(&mut self, game: &mut Game) -> &Vec<Block> {
+        if !game.phase().is_falling() {
+            self.key = None;
+            self.blocks.clear();
+            return &self.blocks;
+        }
+        let preview = *game.rules().preview();
+        let key = (
+            *game.stats().pieces(),
+            game.field().clone(),
+            game.tetromino().kind(),
+            game.hold().tetromino().copied(),
+            game.next_kinds(preview),
+        );
+        if self.key.as_ref() != Some(&key) {
+            self.blocks = self
+                .heuristic
+                .best(game.field(), game.tetromino())
+                .map(|placement| placement.blocks().clone())
+                .unwrap_or_default();
+            self.key = Some(key);
+        }
+        &self.blocks
+    }
+
+    pub fn blocks(&self) -> &Vec<Block> {
+        &self.blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        game_mode::{dig::Dig, endless::Endless},
+        input::{Action, Input},
+    };
+
+    #[test]
+    fn test_update() {
+        let mut game = Game::new(Box::new(Endless));
+        let mut hint = Hint::new(Rules::modern(), Weights::default());
+        let blocks = hint.update(&mut game).clone();
+        assert_eq!(4, blocks.len());
+        assert!(game.field().is_vacant(&blocks));
+        assert!(blocks.iter().any(|block| *block.y() == 0));
+
+        game.key_event(Input::new(vec![Action::MoveLeft]));
+        game.key_event(Input::default());
+        assert_eq!(&blocks, hint.update(&mut game));
+
+        game.key_event(Input::new(vec![Action::Hold]));
+        let color = *game.tetromino().blocks()[0].color();
+        assert_ne!(blocks[0].color(), &color);
+        assert_eq!(&color, hint.update(&mut game)[0].color());
+
+        game.key_event(Input::new(vec![Action::HardDrop]));
+        assert!(hint.update(&mut game).is_empty());
+    }
+
+    #[test]
+    fn test_update_garbage() {
+        let mut game = Game::new(Box::new(Dig::new(0, 1, 2, 0, 1)));
+        let mut hint = Hint::new(Rules::modern(), Weights::default());
+        game.update();
+        let blocks = hint.update(&mut game).clone();
+        assert!(blocks.iter().any(|block| *block.y() == 0));
+        game.update();
+        assert_eq!(9, game.field().blocks().len());
+        assert!(hint.update(&mut game).iter().all(|block| *block.y() > 0));
+    }
+}
@@ -0,0 +1,64 @@
+use crate::models::tetromino::TetrominoKind;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Hold {
+    tetromino: Option<TetrominoKind>,
+    can_hold: bool,
+}
+
+impl Hold {
+    pub fn new() -> Self {
+        Self {
+            tetromino: None,
+            can_hold: true,
+        }
+    }
+
+    pub fn tetromino(&self) -> Option<&TetrominoKind> {
+        self.tetromino.as_ref()
+    }
+
+    pub fn can_hold(&self) -> bool {
+        self.can_hold
+    }
+
+    pub fn swap(&mut self, kind: TetrominoKind) -> Option<TetrominoKind> {
+        self.can_hold = false;
+        self.tetromino.replace(kind)
+    }
+
+    pub fn release(&mut self) {
+        self.can_hold = true;
+    }
+}
+
+impl Default for Hold {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap() {
+        let mut hold = Hold::new();
+        assert!(hold.can_hold());
+        assert_eq!(None, hold.swap(TetrominoKind::T));
+        assert_eq!(Some(&TetrominoKind::T), hold.tetromino());
+        assert!(!hold.can_hold());
+    }
+
+    #[test]
+    fn test_release() {
+        let mut hold = Hold::new();
+        hold.swap(TetrominoKind::T);
+        hold.release();
+        assert!(hold.can_hold());
+        assert_eq!(Some(TetrominoKind::T), hold.swap(TetrominoKind::I));
+        hold.release();
+        assert_eq!(Some(TetrominoKind::I), hold.swap(TetrominoKind::O));
+    }
+}
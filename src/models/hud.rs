@@ -0,0 +1,238 @@
+use crate::models::{
+    combo::ClearEvent,
+    layout::{HudItem, Layout},
+    menu::Menu,
+    rotation_system::RotationSystem,
+    stats::Stats,
+    tetromino::TetrominoKind,
+};
+
+use derive_new::new;
+
+const X: f64 = 336.0;
+const LINE_HEIGHT: f64 = 20.0;
+const PREVIEW_SIZE: f64 = 16.0;
+const MENU_ROWS: usize = 12;
+
+#[derive(Clone, Debug, Eq, new, PartialEq)]
+pub struct Hud {
+    layout: Layout,
+    rotation_system: RotationSystem,
+}
+
+impl Hud {
+    pub fn draw(
+        &self,
+        context: &web_sys::CanvasRenderingContext2d,
+        image: &web_sys::HtmlImageElement,
+        stats: &Stats,
+        hold: Option<&TetrominoKind>,
+        next: &[TetrominoKind],
+        status: &[String],
+    ) {
+        context.set_font("16px monospace");
+        for (item, y) in self.layout.positions() {
+            match item {
+                HudItem::Hold => {
+                    Self::draw_text(context, "HOLD", y);
+                    if let Some(kind) = hold {
+                        self.draw_tetromino(context, image, *kind, y + LINE_HEIGHT);
+                    }
+                }
+                HudItem::Next => {
+                    Self::draw_text(context, "NEXT", y);
+                    for (idx, kind) in next.iter().enumerate() {
+                        let y = y + LINE_HEIGHT + idx as f64 * 3.0 * PREVIEW_SIZE;
+                        self.draw_tetromino(context, image, *kind, y);
+                    }
+                }
+                HudItem::Score => {
+                    Self::draw_text(context, "SCORE", y);
+                    Self::draw_text(context, &stats.score().to_string(), y + LINE_HEIGHT);
+                }
+                HudItem::Level => {
+                    Self::draw_text(context, "LEVEL", y);
+                    Self::draw_text(context, &stats.level().to_string(), y + LINE_HEIGHT);
+                }
+                HudItem::CappedLevel(cap) => {
+                    Self::draw_text(context, "LEVEL", y);
+                    let level = stats.level().min(cap);
+                    Self::draw_text(context, &level.to_string(), y + LINE_HEIGHT);
+                }
+                HudItem::Lines => {
+                    Self::draw_text(context, "LINES", y);
+                    Self::draw_text(context, &stats.lines().to_string(), y + LINE_HEIGHT);
+                }
+                HudItem::Time => {
+                    Self::draw_text(context, "TIME", y);
+                    let time = Self::format_millis(stats.elapsed_millis());
+                    Self::draw_text(context, &time, y + LINE_HEIGHT);
+                }
+                HudItem::Countdown(millis) => {
+                    Self::draw_text(context, "TIME LEFT", y);
+                    let left = (millis - stats.elapsed_millis()).max(0);
+                    Self::draw_text(context, &Self::format_millis(left), y + LINE_HEIGHT);
+                }
+                HudItem::Goal(lines) => {
+                    Self::draw_text(context, "LINES LEFT", y);
+                    let left = (lines - stats.lines()).max(0);
+                    Self::draw_text(context, &left.to_string(), y + LINE_HEIGHT);
+                }
+                HudItem::Pps => {
+                    Self::draw_text(context, "PPS", y);
+                    let pps = format!("{:.2}", stats.pps());
+                    Self::draw_text(context, &pps, y + LINE_HEIGHT);
+                }
+                HudItem::Finesse => {
+                    Self::draw_text(context, "FINESSE", y);
+                    Self::draw_text(context, &stats.faults().to_string(), y + LINE_HEIGHT);
+                }
+                HudItem::Action => {
+                    if let Some(event) = stats.last_event() {
+                        let (action, bonus) = Self::describe(event);
+                        Self::draw_text(context, &action, y);
+                        Self::draw_text(context, &bonus, y + LINE_HEIGHT);
+                    }
+                }
+                HudItem::Status => {
+                    for (idx, line) in status.iter().enumerate() {
+                        Self::draw_text(context, line, y + idx as f64 * LINE_HEIGHT);
+                    }
+                }
+            }
+        }
+    }
+
+    // Replaces the HUD panel so that the revealed stack stays visible next to the result.
+    pub fn draw_result(&self, context: &web_sys::CanvasRenderingContext2d, result: &[String]) {
+        context.clear_rect(320.0, 0.0, 192.0, 640.0);
+        for (idx, line) in result.iter().enumerate() {
+            context
+                .fill_text(line, X, 160.0 + idx as f64 * LINE_HEIGHT * 1.5)
+                .expect("Failed to draw text");
+        }
+    }
+
+    pub fn draw_menu(
+        &self,
+        context: &web_sys::CanvasRenderingContext2d,
+        menu: &Menu,
+        rotation_system: Option<&RotationSystem>,
+    ) {
+        context.set_font("16px monospace");
+        context
+            .fill_text(menu.title(), 32.0, 160.0)
+            .expect("Failed to draw text");
+        let visible = menu.visible(MENU_ROWS);
+        let rows = visible.len();
+        for (row, idx) in visible.enumerate() {
+            let cursor = if idx == *menu.cursor() { ">" } else { " " };
+            context
+                .fill_text(
+                    &format!("{} {}", cursor, menu.items()[idx]),
+                    32.0,
+                    160.0 + (row + 2) as f64 * LINE_HEIGHT * 1.5,
+                )
+                .expect("Failed to draw text");
+        }
+        context
+            .fill_text(
+                &format!(
+                    "ROTATION < {} >",
+                    rotation_system.map_or("DEFAULT", |rotation_system| rotation_system.name())
+                ),
+                32.0,
+                160.0 + (rows + 3) as f64 * LINE_HEIGHT * 1.5,
+            )
+            .expect("Failed to draw text");
+    }
+
+    pub fn format_millis(millis: i32) -> String {
+        format!(
+            "{}:{:02}.{:03}",
+            millis / 60000,
+            millis / 1000 % 60,
+            millis % 1000
+        )
+    }
+}
+
+impl Hud {
+    fn draw_text(context: &web_sys::CanvasRenderingContext2d, text: &str, y: f64) {
+        context
+            .fill_text(text, X, y + LINE_HEIGHT - 4.0)
+            .expect("Failed to draw text");
+    }
+
+    fn draw_tetromino(
+        &self,
+        context: &web_sys::CanvasRenderingContext2d,
+        image: &web_sys::HtmlImageElement,
+        kind: TetrominoKind,
+        y: f64,
+    ) {
+        for block in self.rotation_system.build_tetromino(kind).blocks().iter() {
+            block.draw_scaled(
+                context,
+                image,
+                X + (*block.x() - 3) as f64 * PREVIEW_SIZE,
+                y + (21 - *block.y()) as f64 * PREVIEW_SIZE,
+                PREVIEW_SIZE,
+            );
+        }
+    }
+
+    fn describe(event: &ClearEvent) -> (String, String) {
+        let mut action = Vec::new();
+        if event.is_difficult() && *event.back_to_back() > 0 {
+            action.push("B2B");
+        }
+        if *event.t_spin() {
+            action.push("T-SPIN");
+        }
+        match event.lines() {
+            1 => action.push("SINGLE"),
+            2 => action.push("DOUBLE"),
+            3 => action.push("TRIPLE"),
+            4 => action.push("TETRIS"),
+            _ => {}
+        }
+        let bonus = if *event.all_clear() {
+            "ALL CLEAR".to_string()
+        } else if *event.combo() > 0 {
+            format!("{} COMBO", event.combo())
+        } else {
+            String::new()
+        };
+        (action.join(" "), bonus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{clear::Clear, combo::Combo};
+
+    #[test]
+    fn test_format_millis() {
+        assert_eq!("0:00.000", Hud::format_millis(0));
+        assert_eq!("1:05.250", Hud::format_millis(65250));
+    }
+
+    #[test]
+    fn test_describe() {
+        let mut combo = Combo::new();
+        let event = combo.update(Clear::new(4, false), false);
+        assert_eq!(("TETRIS".to_string(), String::new()), Hud::describe(&event));
+        let event = combo.update(Clear::new(2, true), true);
+        assert_eq!(
+            ("B2B T-SPIN DOUBLE".to_string(), "ALL CLEAR".to_string()),
+            Hud::describe(&event)
+        );
+        let event = combo.update(Clear::new(1, false), false);
+        assert_eq!(
+            ("SINGLE".to_string(), "2 COMBO".to_string()),
+            Hud::describe(&event)
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use derive_new::new;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    SoftDrop,
+    RotateLeft,
+    RotateRight,
+    RotateHalf,
+    Hold,
+    HardDrop,
+}
+
+#[derive(Clone, Debug, Default, Eq, new, PartialEq)]
+pub struct Input(Vec<Action>);
+
+impl Input {
+    pub fn is_pressed(&self, action: Action) -> bool {
+        self.0.contains(&action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_pressed() {
+        let input = Input::new(vec![Action::MoveLeft, Action::Hold]);
+        assert!(input.is_pressed(Action::MoveLeft));
+        assert!(input.is_pressed(Action::Hold));
+        assert!(!input.is_pressed(Action::MoveRight));
+        assert!(!Input::default().is_pressed(Action::MoveLeft));
+    }
+}
@@ -0,0 +1,92 @@
+use derive_new::new;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HudItem {
+    Hold,
+    Next,
+    Score,
+    Level,
+    CappedLevel(i32),
+    Lines,
+    Time,
+    Countdown(i32),
+    Goal(i32),
+    Pps,
+    Finesse,
+    Action,
+    Status,
+}
+
+#[derive(Clone, Debug, Eq, new, PartialEq)]
+pub struct Layout(Vec<HudItem>);
+
+impl Layout {
+    pub fn standard() -> Self {
+        Self(vec![
+            HudItem::Hold,
+            HudItem::Next,
+            HudItem::Score,
+            HudItem::Level,
+            HudItem::Lines,
+            HudItem::Time,
+            HudItem::Pps,
+            HudItem::Action,
+        ])
+    }
+
+    pub fn positions(&self) -> Vec<(HudItem, f64)> {
+        let mut y = 0.0;
+        self.0
+            .iter()
+            .map(|item| {
+                let position = (*item, y);
+                y += item.height();
+                position
+            })
+            .collect()
+    }
+}
+
+impl HudItem {
+    fn height(&self) -> f64 {
+        match self {
+            HudItem::Hold => 72.0,
+            HudItem::Next => 264.0,
+            HudItem::Status => 84.0,
+            _ => 44.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions() {
+        let layout = Layout::new(vec![HudItem::Score, HudItem::Hold, HudItem::Lines]);
+        assert_eq!(
+            vec![
+                (HudItem::Score, 0.0),
+                (HudItem::Hold, 44.0),
+                (HudItem::Lines, 116.0)
+            ],
+            layout.positions()
+        );
+    }
+
+    #[test]
+    fn test_positions_with_goal() {
+        let layout = Layout::new(vec![HudItem::Goal(40), HudItem::Countdown(1000)]);
+        assert_eq!(
+            vec![(HudItem::Goal(40), 0.0), (HudItem::Countdown(1000), 44.0)],
+            layout.positions()
+        );
+    }
+
+    #[test]
+    fn test_standard() {
+        let (_, y) = *Layout::standard().positions().last().unwrap();
+        assert!(y + HudItem::Action.height() <= 640.0);
+    }
+}
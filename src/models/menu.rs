@@ -0,0 +1,90 @@
+use std::ops::Range;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Menu {
+    title: String,
+    items: Vec<String>,
+    cursor: usize,
+}
+
+impl Menu {
+    pub fn new(title: String, items: Vec<String>) -> Self {
+        Self {
+            title,
+            items,
+            cursor: 0,
+        }
+    }
+
+    pub fn title(&self) -> &String {
+        &self.title
+    }
+
+    pub fn items(&self) -> &Vec<String> {
+        &self.items
+    }
+
+    pub fn cursor(&self) -> &usize {
+        &self.cursor
+    }
+
+    // Scrolls just far enough to keep the cursor within `rows` visible items.
+    pub fn visible(&self, rows: usize) -> Range<usize> {
+        let start = (self.cursor + 1)
+            .saturating_sub(rows)
+            .min(self.items.len().saturating_sub(rows));
+        start..self.items.len().min(start + rows)
+    }
+
+    pub fn up(&mut self) {
+        self.cursor = (self.cursor + self.items.len() - 1) % self.items.len();
+    }
+
+    pub fn down(&mut self) {
+        self.cursor = (self.cursor + 1) % self.items.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_up() {
+        let mut menu = build_menu();
+        menu.up();
+        assert_eq!(2, *menu.cursor());
+        menu.up();
+        assert_eq!(1, *menu.cursor());
+    }
+
+    #[test]
+    fn test_down() {
+        let mut menu = build_menu();
+        menu.down();
+        assert_eq!(1, *menu.cursor());
+        menu.down();
+        menu.down();
+        assert_eq!(0, *menu.cursor());
+    }
+
+    #[test]
+    fn test_visible() {
+        let mut menu = build_menu();
+        assert_eq!(0..3, menu.visible(5));
+        assert_eq!(0..2, menu.visible(2));
+        menu.down();
+        assert_eq!(0..2, menu.visible(2));
+        menu.down();
+        assert_eq!(1..3, menu.visible(2));
+        menu.down();
+        assert_eq!(0..2, menu.visible(2));
+    }
+
+    fn build_menu() -> Menu {
+        Menu::new(
+            "MENU".to_string(),
+            vec!["A".to_string(), "B".to_string(), "C".to_string()],
+        )
+    }
+}
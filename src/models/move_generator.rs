@@ -0,0 +1,211 @@
+use crate::models::{
+    block::Block,
+    clear::Clear,
+    field::Field,
+    input::Action,
+    rules::Rules,
+    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection, TetrominoKind},
+};
+
+use derive_new::new;
+use std::collections::{HashSet, VecDeque};
+
+const STEPS: [Action; 6] = [
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::RotateLeft,
+    Action::RotateRight,
+    Action::RotateHalf,
+    Action::SoftDrop,
+];
+
+#[derive(Clone, Debug, Eq, new, PartialEq)]
+pub struct Placement {
+    kind: TetrominoKind,
+    dir: TetrominoDirection,
+    blocks: Vec<Block>,
+    path: Vec<Action>,
+}
+
+impl Placement {
+    pub fn kind(&self) -> &TetrominoKind {
+        &self.kind
+    }
+
+    pub fn dir(&self) -> &TetrominoDirection {
+        &self.dir
+    }
+
+    pub fn blocks(&self) -> &Vec<Block> {
+        &self.blocks
+    }
+
+    pub fn path(&self) -> &Vec<Action> {
+        &self.path
+    }
+
+    pub fn apply(&self, field: &Field) -> (Field, Clear) {
+        let mut field = field.clone();
+        field.fix_blocks(self.blocks.clone());
+        let clear = field.clear_blocks();
+        (field, clear)
+    }
+
+    pub fn is_rotated(&self) -> bool {
+        match self
+            .path
+            .iter()
+            .rev()
+            .find(|action| **action != Action::HardDrop)
+        {
+            Some(action) => {
+                [Action::RotateLeft, Action::RotateRight, Action::RotateHalf].contains(action)
+            }
+            None => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, new, PartialEq)]
+pub struct MoveGenerator {
+    rules: Rules,
+}
+
+impl MoveGenerator {
+    pub fn generate(&self, field: &Field, tetromino: &dyn Tetromino) -> Vec<Placement> {
+        let mut placements = Vec::new();
+        if !field.is_vacant(&tetromino.blocks()) {
+            return placements;
+        }
+
+        let mut visited = HashSet::new();
+        let mut placed = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(Self::state(tetromino));
+        queue.push_back((tetromino.clone_box(), Vec::new()));
+
+        while let Some((tetromino, path)) = queue.pop_front() {
+            if !field.is_vacant(&tetromino.dry_move(MoveDirection::Down)) {
+                let blocks = Self::sort(tetromino.blocks());
+                if placed.insert(blocks.clone()) {
+                    placements.push(Placement::new(
+                        tetromino.kind(),
+                        tetromino.direction(),
+                        blocks,
+                        self.finish(&path),
+                    ));
+                }
+            }
+            for step in STEPS.iter() {
+                let mut next = tetromino.clone();
+                if !self.apply(next.as_mut(), *step, field) {
+                    continue;
+                }
+                if visited.insert(Self::state(next.as_ref())) {
+                    let mut path = path.clone();
+                    path.push(*step);
+                    queue.push_back((next, path));
+                }
+            }
+        }
+        placements
+    }
+}
+
+impl MoveGenerator {
+    fn apply(&self, tetromino: &mut dyn Tetromino, step: Action, field: &Field) -> bool {
+        let kicks = *self.rules.kicks();
+        match step {
+            Action::MoveLeft => tetromino.try_move(MoveDirection::Left, field),
+            Action::MoveRight => tetromino.try_move(MoveDirection::Right, field),
+            Action::SoftDrop => tetromino.try_move(MoveDirection::Down, field),
+            Action::RotateLeft => tetromino.try_rotate(RotateDirection::Left, field, kicks),
+            Action::RotateRight => tetromino.try_rotate(RotateDirection::Right, field, kicks),
+            Action::RotateHalf => tetromino.try_rotate(RotateDirection::Half, field, kicks),
+            _ => false,
+        }
+    }
+
+    fn finish(&self, path: &[Action]) -> Vec<Action> {
+        let mut path = path.to_vec();
+        if *self.rules.hard_drop() {
+            while path.last() == Some(&Action::SoftDrop) {
+                path.pop();
+            }
+            path.push(Action::HardDrop);
+        }
+        path
+    }
+
+    fn state(tetromino: &dyn Tetromino) -> (Vec<Block>, TetrominoDirection) {
+        (Self::sort(tetromino.blocks()), tetromino.direction())
+    }
+
+    fn sort(mut blocks: Vec<Block>) -> Vec<Block> {
+        blocks.sort_by_key(|block| (*block.y(), *block.x()));
+        blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        block::Color, game_mode::puzzle::Puzzle, game_mode::GameMode,
+        tetromino_factory::TetrominoFactory,
+    };
+
+    #[test]
+    fn test_generate_1() {
+        let generator = MoveGenerator::new(Rules::modern());
+        let field = Field::new(vec![vec![None; 10]; 24]);
+        for (kind, num) in [
+            (TetrominoKind::O, 9),
+            (TetrominoKind::I, 17),
+            (TetrominoKind::S, 17),
+            (TetrominoKind::T, 34),
+        ]
+        .iter()
+        {
+            let tetromino = TetrominoFactory::build_tetromino(*kind);
+            let placements = generator.generate(&field, tetromino.as_ref());
+            assert_eq!(*num, placements.len());
+            assert!(placements
+                .iter()
+                .all(|placement| placement.path().last() == Some(&Action::HardDrop)));
+        }
+    }
+
+    #[test]
+    fn test_generate_2() {
+        let generator = MoveGenerator::new(Rules::modern());
+        let text = "name: A\ngoal: tsd\nqueue: T\nfield:\n....XXXXXX\nXX...XXXXX\nXXX.XXXXXX\n";
+        let field = Puzzle::parse_pack(text).unwrap()[0].initial_field();
+        let tetromino = TetrominoFactory::build_tetromino(TetrominoKind::T);
+        let placements = generator.generate(&field, tetromino.as_ref());
+        let blocks = vec![
+            Block::new(Color::Purple, 3, 0),
+            Block::new(Color::Purple, 2, 1),
+            Block::new(Color::Purple, 3, 1),
+            Block::new(Color::Purple, 4, 1),
+        ];
+        let placement = placements
+            .iter()
+            .find(|placement| *placement.blocks() == blocks)
+            .unwrap();
+        assert!(placement.is_rotated());
+        assert_eq!(TetrominoDirection::Left, *placement.dir());
+    }
+
+    #[test]
+    fn test_generate_3() {
+        let generator = MoveGenerator::new(Rules::classic(0, 1));
+        let field = Field::new(vec![vec![None; 10]; 24]);
+        let tetromino = TetrominoFactory::build_tetromino(TetrominoKind::O);
+        let placements = generator.generate(&field, tetromino.as_ref());
+        assert_eq!(9, placements.len());
+        assert!(placements
+            .iter()
+            .all(|placement| placement.path().last() == Some(&Action::SoftDrop)));
+    }
+}
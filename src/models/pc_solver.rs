@@ -0,0 +1,352 @@
+use crate::models::{
+    block::Block,
+    field::Field,
+    input::Action,
+    move_generator::{MoveGenerator, Placement},
+    rules::Rules,
+    tetromino::{Tetromino, TetrominoKind},
+};
+
+use std::collections::HashSet;
+
+const WIDTH: i32 = 10;
+const MAX_HEIGHT: i32 = 12;
+const ROW: u128 = (1 << WIDTH) - 1;
+const LEFT_COLUMN: u128 = 0x4010_0401_0040_1004_0100_4010_0401;
+const RIGHT_COLUMN: u128 = LEFT_COLUMN << (WIDTH - 1);
+
+pub struct PcSolver {
+    rules: Rules,
+    generator: MoveGenerator,
+}
+
+impl PcSolver {
+    pub fn new(rules: Rules) -> Self {
+        Self {
+            rules,
+            generator: MoveGenerator::new(rules),
+        }
+    }
+
+    // Returns the placements, in play order, that empty the field without stacking above
+    // `height`. A placement's path starts with a hold when the piece comes from the hold slot.
+    pub fn solve(
+        &self,
+        field: &Field,
+        queue: &[TetrominoKind],
+        hold: Option<TetrominoKind>,
+        height: i32,
+    ) -> Option<Vec<Placement>> {
+        let blocks = field.blocks();
+        if blocks.iter().any(|block| *block.y() >= MAX_HEIGHT) {
+            return None;
+        }
+        let bits = Self::bits(&blocks);
+        (Self::top(bits).max(1)..=height.min(MAX_HEIGHT))
+            .filter(|height| (WIDTH * height - bits.count_ones() as i32) % 4 == 0)
+            .find_map(|height| {
+                let mut failed = HashSet::new();
+                let mut steps = Vec::new();
+                if self.search(field, height, queue, hold, &mut failed, &mut steps) {
+                    self.solution(field, steps)
+                } else {
+                    None
+                }
+            })
+    }
+}
+
+impl PcSolver {
+    fn search(
+        &self,
+        field: &Field,
+        height: i32,
+        queue: &[TetrominoKind],
+        hold: Option<TetrominoKind>,
+        failed: &mut HashSet<(u128, i32, Option<TetrominoKind>, usize)>,
+        steps: &mut Vec<(bool, Placement)>,
+    ) -> bool {
+        let bits = Self::bits(&field.blocks());
+        let pieces = (WIDTH * height - bits.count_ones() as i32) / 4;
+        if pieces > queue.len() as i32 + hold.is_some() as i32 {
+            return false;
+        }
+
+        for (kind, hold, queue, held) in Self::options(queue, hold) {
+            let tetromino = self.build_tetromino(kind, Self::top(bits));
+            let mut placements: Vec<_> = self
+                .generator
+                .generate(field, tetromino.as_ref())
+                .into_iter()
+                .filter(|placement| placement.blocks().iter().all(|block| *block.y() < height))
+                .collect();
+            placements.sort_by_key(|placement| Self::bottom(placement.blocks()));
+            for placement in placements {
+                let (next, lines) = Self::clear(bits | Self::bits(placement.blocks()), height);
+                if next == 0 {
+                    steps.push((held, placement));
+                    return true;
+                }
+                let height = height - lines;
+                if !Self::is_fillable(next, height) {
+                    continue;
+                }
+                if !failed.insert((next, height, hold, queue.len())) {
+                    continue;
+                }
+                steps.push((held, placement));
+                let (field, _) = steps.last().unwrap().1.apply(field);
+                if self.search(&field, height, queue, hold, failed, steps) {
+                    return true;
+                }
+                steps.pop();
+            }
+        }
+        false
+    }
+
+    fn solution(&self, field: &Field, steps: Vec<(bool, Placement)>) -> Option<Vec<Placement>> {
+        let mut field = field.clone();
+        let mut solution = Vec::new();
+        for (held, placement) in steps {
+            let mut path = if held { vec![Action::Hold] } else { Vec::new() };
+            path.extend(self.spawn_path(&field, &placement)?);
+            solution.push(Placement::new(
+                *placement.kind(),
+                *placement.dir(),
+                placement.blocks().clone(),
+                path,
+            ));
+            field = placement.apply(&field).0;
+        }
+        Some(solution)
+    }
+
+    // Starts the search just above the stack; the empty rows above it only add soft drops.
+    fn build_tetromino(&self, kind: TetrominoKind, top: i32) -> Box<dyn Tetromino> {
+        let mut tetromino = self.rules.build_tetromino(kind);
+        let bottom = Self::bottom(&tetromino.blocks());
+        tetromino.shift(0, (top + 3 - bottom).min(0));
+        tetromino
+    }
+
+    fn spawn_path(&self, field: &Field, placement: &Placement) -> Option<Vec<Action>> {
+        let tetromino = self.rules.build_tetromino(*placement.kind());
+        self.generator
+            .generate(field, tetromino.as_ref())
+            .into_iter()
+            .find(|spawned| spawned.blocks() == placement.blocks())
+            .map(|spawned| spawned.path().clone())
+    }
+
+    fn bottom(blocks: &[Block]) -> i32 {
+        blocks.iter().map(|block| *block.y()).min().unwrap()
+    }
+
+    fn options(
+        queue: &[TetrominoKind],
+        hold: Option<TetrominoKind>,
+    ) -> Vec<(TetrominoKind, Option<TetrominoKind>, &[TetrominoKind], bool)> {
+        let mut options = Vec::new();
+        let (current, rest) = match queue.split_first() {
+            Some(split) => split,
+            None => return options,
+        };
+        options.push((*current, hold, rest, false));
+        match hold {
+            Some(kind) if kind != *current => options.push((kind, Some(*current), rest, true)),
+            Some(_) => {}
+            None => {
+                if let Some((next, rest)) = rest.split_first() {
+                    options.push((*next, Some(*current), rest, true));
+                }
+            }
+        }
+        options
+    }
+
+    fn bits(blocks: &[Block]) -> u128 {
+        blocks
+            .iter()
+            .map(|block| 1 << (*block.y() * WIDTH + *block.x()))
+            .fold(0, |bits, bit| bits | bit)
+    }
+
+    fn top(bits: u128) -> i32 {
+        (128 - bits.leading_zeros() as i32 + WIDTH - 1) / WIDTH
+    }
+
+    fn clear(bits: u128, height: i32) -> (u128, i32) {
+        let mut cleared = 0;
+        let mut lines = 0;
+        for y in 0..height {
+            let row = bits >> (y * WIDTH) & ROW;
+            if row == ROW {
+                lines += 1;
+            } else {
+                cleared |= row << ((y - lines) * WIDTH);
+            }
+        }
+        (cleared, lines)
+    }
+
+    // Every enclosed empty region below the height must be fillable with whole tetrominoes.
+    fn is_fillable(bits: u128, height: i32) -> bool {
+        if height <= 0 {
+            return false;
+        }
+        let mut empty = !bits & ((1 << (height * WIDTH)) - 1);
+        while empty != 0 {
+            let mut region = empty & empty.wrapping_neg();
+            loop {
+                let grown = (region
+                    | (region << 1 & !LEFT_COLUMN)
+                    | (region >> 1 & !RIGHT_COLUMN)
+                    | region << WIDTH
+                    | region >> WIDTH)
+                    & empty;
+                if grown == region {
+                    break;
+                }
+                region = grown;
+            }
+            if !region.count_ones().is_multiple_of(4) {
+                return false;
+            }
+            empty &= !region;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        game::Game,
+        game_mode::{
+            puzzle::{Goal, Puzzle},
+            GameMode,
+        },
+        input::Input,
+    };
+
+    #[test]
+    fn test_solve() {
+        let solver = PcSolver::new(Rules::modern());
+        let field = build_field("XXXXXX....\nXXXXXX....\n");
+        let queue = [TetrominoKind::I, TetrominoKind::I];
+        let solution = solver.solve(&field, &queue, None, 4).unwrap();
+        assert_eq!(2, solution.len());
+
+        let solution = solver
+            .solve(
+                &field,
+                &[TetrominoKind::T, TetrominoKind::O],
+                Some(TetrominoKind::O),
+                2,
+            )
+            .unwrap();
+        assert_eq!(2, solution.len());
+        assert_eq!(Some(&Action::Hold), solution[0].path().first());
+    }
+
+    #[test]
+    fn test_solve_impossible() {
+        let solver = PcSolver::new(Rules::modern());
+        let field = build_field("XXXXXX....\nXXXXXX....\n");
+        assert_eq!(
+            None,
+            solver.solve(&field, &[TetrominoKind::T, TetrominoKind::T], None, 2)
+        );
+        assert_eq!(None, solver.solve(&field, &[TetrominoKind::I], None, 2));
+        assert_eq!(
+            None,
+            solver.solve(&field, &[TetrominoKind::I, TetrominoKind::I], None, 1)
+        );
+        assert_eq!(
+            None,
+            solver.solve(&build_field("XXXXXXX...\n"), &[TetrominoKind::I], None, 1)
+        );
+        let tall = format!("X.........\n{}", "..........\n".repeat(13));
+        assert_eq!(
+            None,
+            solver.solve(&build_field(&tall), &[TetrominoKind::I], None, 20)
+        );
+    }
+
+    #[test]
+    fn test_solve_opener() {
+        let solver = PcSolver::new(Rules::modern());
+        let queue = [
+            TetrominoKind::L,
+            TetrominoKind::O,
+            TetrominoKind::J,
+            TetrominoKind::I,
+            TetrominoKind::O,
+        ];
+        let field = Field::new(vec![vec![None; 10]; 24]);
+        let solution = solver.solve(&field, &queue, None, 2).unwrap();
+        assert_eq!(5, solution.len());
+        assert_eq!(None, solver.solve(&field, &queue, None, 1));
+
+        let mut game = Game::new(Box::new(build_puzzle(&field, &queue)));
+        for placement in solution.iter() {
+            for action in placement.path() {
+                game.key_event(Input::new(vec![*action]));
+                game.key_event(Input::default());
+            }
+            while !game.phase().is_falling() && !game.phase().is_finished() {
+                game.update();
+            }
+        }
+        assert!(game.field().blocks().is_empty());
+    }
+
+    #[test]
+    fn test_spawn_path() {
+        let solver = PcSolver::new(Rules::modern());
+        let field = build_field("XXXX......\n..X.......\n..X.......\n");
+        let mut tetromino = Rules::modern().build_tetromino(TetrominoKind::O);
+        let blocks = tetromino.blocks();
+        let left = blocks.iter().map(|block| *block.x()).min().unwrap();
+        tetromino.shift(-left, -PcSolver::bottom(&blocks));
+        let placement = Placement::new(
+            TetrominoKind::O,
+            tetromino.direction(),
+            tetromino.blocks(),
+            Vec::new(),
+        );
+        assert_eq!(None, solver.spawn_path(&field, &placement));
+    }
+
+    #[test]
+    fn test_clear() {
+        let bits = PcSolver::bits(&build_field("X.........\nXXXXXXXXXX\n").blocks());
+        assert_eq!((1, 1), PcSolver::clear(bits, 2));
+    }
+
+    #[test]
+    fn test_is_fillable() {
+        let bits = |rows| PcSolver::bits(&build_field(rows).blocks());
+        assert!(PcSolver::is_fillable(bits("XXXXXX....\n"), 1));
+        assert!(!PcSolver::is_fillable(bits("XXX.XX....\n"), 1));
+        assert!(!PcSolver::is_fillable(bits("XXXXXX....\n"), 0));
+        assert!(PcSolver::is_fillable(bits("XX..XXXX..\nXX..XXXX..\n"), 2));
+        assert!(!PcSolver::is_fillable(bits("X...XXXX..\nX...XXXX..\n"), 2));
+    }
+
+    fn build_field(rows: &str) -> Field {
+        let text = format!("name: A\ngoal: perfect\nqueue: I\nfield:\n{}", rows);
+        Puzzle::parse_pack(&text).unwrap()[0].initial_field()
+    }
+
+    fn build_puzzle(field: &Field, queue: &[TetrominoKind]) -> Puzzle {
+        Puzzle::new(
+            "A".to_string(),
+            field.clone(),
+            queue.to_vec(),
+            Goal::PerfectClear,
+        )
+    }
+}
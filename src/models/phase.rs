@@ -0,0 +1,69 @@
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Phase {
+    Falling,
+    LineClear(Vec<i32>, i32),
+    Entry(i32),
+    Finished,
+}
+
+impl Phase {
+    pub fn tick(&mut self) {
+        match self {
+            Phase::Falling | Phase::Finished => {}
+            Phase::LineClear(_, frames) | Phase::Entry(frames) => *frames += 1,
+        }
+    }
+
+    pub fn is_falling(&self) -> bool {
+        *self == Phase::Falling
+    }
+
+    pub fn is_finished(&self) -> bool {
+        *self == Phase::Finished
+    }
+
+    pub fn is_flashing(&self, row_idx: i32) -> bool {
+        match self {
+            Phase::LineClear(rows, frames) => rows.contains(&row_idx) && frames / 4 % 2 == 0,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick() {
+        let mut phase = Phase::Falling;
+        phase.tick();
+        assert_eq!(Phase::Falling, phase);
+
+        let mut phase = Phase::LineClear(vec![0], 0);
+        phase.tick();
+        assert_eq!(Phase::LineClear(vec![0], 1), phase);
+
+        let mut phase = Phase::Entry(5);
+        phase.tick();
+        assert_eq!(Phase::Entry(6), phase);
+
+        let mut phase = Phase::Finished;
+        phase.tick();
+        assert_eq!(Phase::Finished, phase);
+    }
+
+    #[test]
+    fn test_is_flashing() {
+        let phase = Phase::LineClear(vec![0, 2], 0);
+        assert!(phase.is_flashing(0));
+        assert!(!phase.is_flashing(1));
+        assert!(phase.is_flashing(2));
+
+        let phase = Phase::LineClear(vec![0, 2], 4);
+        assert!(!phase.is_flashing(0));
+
+        let phase = Phase::Entry(0);
+        assert!(!phase.is_flashing(0));
+    }
+}
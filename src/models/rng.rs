@@ -0,0 +1,41 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rng(u32);
+
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        Self(seed.max(1))
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+
+    pub fn below(&mut self, max: u32) -> u32 {
+        self.next_u32() % max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_u32() {
+        let mut rng1 = Rng::new(42);
+        let mut rng2 = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(rng1.next_u32(), rng2.next_u32());
+        }
+    }
+
+    #[test]
+    fn test_below() {
+        let mut rng = Rng::new(0);
+        for _ in 0..100 {
+            assert!(rng.below(10) < 10);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::models::{
+    tetromino::{ars::Ars, Tetromino, TetrominoKind},
+    tetromino_factory::TetrominoFactory,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RotationSystem {
+    Srs,
+    Ars,
+}
+
+impl RotationSystem {
+    pub fn name(&self) -> &str {
+        match self {
+            RotationSystem::Srs => "SRS",
+            RotationSystem::Ars => "ARS",
+        }
+    }
+
+    pub fn build_tetromino(&self, kind: TetrominoKind) -> Box<dyn Tetromino> {
+        match self {
+            RotationSystem::Srs => TetrominoFactory::build_tetromino(kind),
+            RotationSystem::Ars => Box::new(Ars::spawn(kind)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_tetromino() {
+        let srs = RotationSystem::Srs.build_tetromino(TetrominoKind::T);
+        let ars = RotationSystem::Ars.build_tetromino(TetrominoKind::T);
+        assert_eq!(TetrominoKind::T, ars.kind());
+        assert!(srs.blocks().iter().any(|block| *block.y() == 21));
+        assert!(ars.blocks().iter().all(|block| *block.y() <= 21));
+        assert_ne!(srs.blocks(), ars.blocks());
+    }
+}
@@ -0,0 +1,189 @@
+use crate::models::{
+    delay::Delay,
+    input::Action,
+    rotation_system::RotationSystem,
+    stats::Scoring,
+    tetromino::{big::Big, Tetromino, TetrominoKind},
+    tetromino_factory::Randomizer,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rules {
+    hold: bool,
+    hard_drop: bool,
+    preview: usize,
+    kicks: bool,
+    rotation_system: RotationSystem,
+    big: bool,
+    randomizer: Randomizer,
+    scoring: Scoring,
+    start_level: i32,
+    das: i32,
+    arr: i32,
+    soft_drop: i32,
+    delay: Delay,
+}
+
+impl Rules {
+    pub fn modern() -> Self {
+        Self {
+            hold: true,
+            hard_drop: true,
+            preview: 5,
+            kicks: true,
+            rotation_system: RotationSystem::Srs,
+            big: false,
+            randomizer: Randomizer::SevenBag,
+            scoring: Scoring::Guideline,
+            start_level: 1,
+            das: 4,
+            arr: 4,
+            soft_drop: 4,
+            delay: Delay::new(6, 20, 0),
+        }
+    }
+
+    pub fn classic(start_level: i32, seed: u32) -> Self {
+        Self {
+            hold: false,
+            hard_drop: false,
+            preview: 1,
+            kicks: false,
+            rotation_system: RotationSystem::Srs,
+            big: false,
+            randomizer: Randomizer::Nes(seed),
+            scoring: Scoring::Nes,
+            start_level,
+            das: 16,
+            arr: 6,
+            soft_drop: 2,
+            delay: Delay::new(10, 18, 0),
+        }
+    }
+
+    pub fn master() -> Self {
+        Self {
+            hold: false,
+            hard_drop: false,
+            preview: 1,
+            kicks: true,
+            rotation_system: RotationSystem::Ars,
+            big: false,
+            randomizer: Randomizer::SevenBag,
+            scoring: Scoring::Guideline,
+            start_level: 1,
+            das: 14,
+            arr: 1,
+            soft_drop: 1,
+            delay: Delay::new(25, 40, 30),
+        }
+    }
+
+    pub fn with_rotation_system(self, rotation_system: RotationSystem) -> Self {
+        Self {
+            rotation_system,
+            ..self
+        }
+    }
+
+    pub fn with_randomizer(self, randomizer: Randomizer) -> Self {
+        Self { randomizer, ..self }
+    }
+
+    pub fn with_big(self, big: bool) -> Self {
+        Self { big, ..self }
+    }
+
+    pub fn hold(&self) -> &bool {
+        &self.hold
+    }
+
+    pub fn hard_drop(&self) -> &bool {
+        &self.hard_drop
+    }
+
+    pub fn preview(&self) -> &usize {
+        &self.preview
+    }
+
+    pub fn kicks(&self) -> &bool {
+        &self.kicks
+    }
+
+    pub fn rotation_system(&self) -> &RotationSystem {
+        &self.rotation_system
+    }
+
+    pub fn big(&self) -> &bool {
+        &self.big
+    }
+
+    pub fn randomizer(&self) -> &Randomizer {
+        &self.randomizer
+    }
+
+    pub fn scoring(&self) -> &Scoring {
+        &self.scoring
+    }
+
+    pub fn start_level(&self) -> &i32 {
+        &self.start_level
+    }
+
+    pub fn delay(&self) -> &Delay {
+        &self.delay
+    }
+
+    pub fn build_tetromino(&self, kind: TetrominoKind) -> Box<dyn Tetromino> {
+        let tetromino = self.rotation_system.build_tetromino(kind);
+        if self.big {
+            Box::new(Big::new(tetromino))
+        } else {
+            tetromino
+        }
+    }
+
+    pub fn is_triggered(&self, action: Action, frames: i32) -> bool {
+        match action {
+            Action::MoveLeft | Action::MoveRight => {
+                frames == 1 || (frames > self.das && (frames - self.das - 1) % self.arr == 0)
+            }
+            Action::SoftDrop => (frames - 1) % self.soft_drop == 0,
+            _ => frames == 1,
+        }
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::modern()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_triggered() {
+        let rules = Rules::classic(0, 0);
+        let frames: Vec<_> = (1..=30)
+            .filter(|frames| rules.is_triggered(Action::MoveLeft, *frames))
+            .collect();
+        assert_eq!(vec![1, 17, 23, 29], frames);
+        assert!(rules.is_triggered(Action::SoftDrop, 3));
+        assert!(!rules.is_triggered(Action::SoftDrop, 4));
+        assert!(rules.is_triggered(Action::RotateLeft, 1));
+        assert!(!rules.is_triggered(Action::RotateLeft, 17));
+    }
+
+    #[test]
+    fn test_build_tetromino() {
+        let rules = Rules::modern();
+        assert_eq!(4, rules.build_tetromino(TetrominoKind::T).blocks().len());
+        let rules = rules.with_big(true);
+        let tetromino = rules.build_tetromino(TetrominoKind::T);
+        assert_eq!(TetrominoKind::T, tetromino.kind());
+        assert_eq!(16, tetromino.blocks().len());
+    }
+}
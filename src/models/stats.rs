@@ -0,0 +1,251 @@
+use crate::models::combo::ClearEvent;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Scoring {
+    Guideline,
+    Nes,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    scoring: Scoring,
+    start_level: i32,
+    score: i32,
+    lines: i32,
+    pieces: i32,
+    frames: i32,
+    faults: i32,
+    topped_out: bool,
+    last_event: Option<ClearEvent>,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Self::with_scoring(Scoring::Guideline, 1)
+    }
+
+    pub fn with_scoring(scoring: Scoring, start_level: i32) -> Self {
+        Self {
+            scoring,
+            start_level,
+            score: 0,
+            lines: 0,
+            pieces: 0,
+            frames: 0,
+            faults: 0,
+            topped_out: false,
+            last_event: None,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.frames += 1;
+    }
+
+    pub fn add_clear(&mut self, event: ClearEvent) {
+        self.score += match self.scoring {
+            Scoring::Guideline => self.guideline_score(&event),
+            Scoring::Nes => self.nes_score(&event),
+        };
+        self.lines += *event.lines();
+        self.pieces += 1;
+        if *event.lines() > 0 || *event.t_spin() {
+            self.last_event = Some(event);
+        }
+    }
+
+    pub fn add_fault(&mut self) {
+        self.faults += 1;
+    }
+
+    pub fn top_out(&mut self) {
+        self.topped_out = true;
+    }
+
+    pub fn score(&self) -> &i32 {
+        &self.score
+    }
+
+    pub fn lines(&self) -> &i32 {
+        &self.lines
+    }
+
+    pub fn pieces(&self) -> &i32 {
+        &self.pieces
+    }
+
+    pub fn frames(&self) -> &i32 {
+        &self.frames
+    }
+
+    pub fn faults(&self) -> &i32 {
+        &self.faults
+    }
+
+    pub fn topped_out(&self) -> &bool {
+        &self.topped_out
+    }
+
+    pub fn last_event(&self) -> Option<&ClearEvent> {
+        self.last_event.as_ref()
+    }
+
+    pub fn level(&self) -> i32 {
+        match self.scoring {
+            Scoring::Guideline => self.start_level + self.lines / 10,
+            Scoring::Nes => {
+                let first = (self.start_level * 10 + 10).min((self.start_level * 10 - 50).max(100));
+                if self.lines < first {
+                    self.start_level
+                } else {
+                    self.start_level + 1 + (self.lines - first) / 10
+                }
+            }
+        }
+    }
+
+    pub fn elapsed_millis(&self) -> i32 {
+        self.frames * 1000 / 60
+    }
+
+    pub fn pps(&self) -> f64 {
+        if self.frames == 0 {
+            0.0
+        } else {
+            self.pieces as f64 * 60.0 / self.frames as f64
+        }
+    }
+}
+
+impl Stats {
+    fn guideline_score(&self, event: &ClearEvent) -> i32 {
+        let base = match (*event.t_spin(), *event.lines()) {
+            (false, 1) => 100,
+            (false, 2) => 300,
+            (false, 3) => 500,
+            (false, 4) => 800,
+            (true, 0) => 400,
+            (true, 1) => 800,
+            (true, 2) => 1200,
+            (true, 3) => 1600,
+            _ => 0,
+        };
+        let base = if event.is_difficult() && *event.back_to_back() > 0 {
+            base * 3 / 2
+        } else {
+            base
+        };
+        let combo = 50 * event.combo().max(&0);
+        let all_clear = match (*event.all_clear(), *event.lines()) {
+            (false, _) => 0,
+            (true, 1) => 800,
+            (true, 2) => 1200,
+            (true, 3) => 1800,
+            (true, _) => 2000,
+        };
+        (base + combo + all_clear) * self.level()
+    }
+
+    fn nes_score(&self, event: &ClearEvent) -> i32 {
+        let base = match *event.lines() {
+            1 => 40,
+            2 => 100,
+            3 => 300,
+            4 => 1200,
+            _ => 0,
+        };
+        base * (self.level() + 1)
+    }
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{clear::Clear, combo::Combo};
+
+    #[test]
+    fn test_add_clear_1() {
+        let mut stats = Stats::new();
+        let mut combo = Combo::new();
+        stats.add_clear(combo.update(Clear::new(0, false), false));
+        assert_eq!(0, *stats.score());
+        assert_eq!(1, *stats.pieces());
+        assert_eq!(None, stats.last_event());
+        stats.add_clear(combo.update(Clear::new(4, false), false));
+        assert_eq!(800, *stats.score());
+        stats.add_clear(combo.update(Clear::new(2, false), true));
+        assert_eq!(800 + 1800 + 50, *stats.score());
+        assert_eq!(6, *stats.lines());
+        assert_eq!(3, *stats.pieces());
+        assert!(stats.last_event().is_some());
+    }
+
+    #[test]
+    fn test_add_clear_2() {
+        let mut stats = Stats::new();
+        let mut combo = Combo::new();
+        for _ in 0..5 {
+            stats.add_clear(combo.update(Clear::new(2, false), false));
+        }
+        assert_eq!(2, stats.level());
+        stats.add_clear(combo.update(Clear::new(1, true), false));
+        assert_eq!(2, stats.level());
+        assert_eq!(2000 + (100 + 250 + 800) * 2, *stats.score());
+    }
+
+    #[test]
+    fn test_add_clear_3() {
+        let mut stats = Stats::with_scoring(Scoring::Nes, 0);
+        let mut combo = Combo::new();
+        stats.add_clear(combo.update(Clear::new(4, false), false));
+        stats.add_clear(combo.update(Clear::new(1, false), true));
+        stats.add_clear(combo.update(Clear::new(4, false), false));
+        assert_eq!(1200 + 40 + 1200, *stats.score());
+        assert_eq!(0, stats.level());
+        stats.add_clear(combo.update(Clear::new(2, false), false));
+        assert_eq!(1, stats.level());
+        stats.add_clear(combo.update(Clear::new(1, false), false));
+        assert_eq!(1200 + 40 + 1200 + 100 + 80, *stats.score());
+    }
+
+    #[test]
+    fn test_level() {
+        let mut combo = Combo::new();
+        let mut stats = Stats::with_scoring(Scoring::Nes, 18);
+        for _ in 0..32 {
+            stats.add_clear(combo.update(Clear::new(4, false), false));
+        }
+        assert_eq!(18, stats.level());
+        stats.add_clear(combo.update(Clear::new(2, false), false));
+        assert_eq!(19, stats.level());
+        for _ in 0..3 {
+            stats.add_clear(combo.update(Clear::new(4, false), false));
+        }
+        assert_eq!(20, stats.level());
+
+        let mut stats = Stats::with_scoring(Scoring::Nes, 5);
+        for _ in 0..25 {
+            stats.add_clear(combo.update(Clear::new(4, false), false));
+        }
+        assert_eq!(10, stats.level());
+    }
+
+    #[test]
+    fn test_pps() {
+        let mut stats = Stats::new();
+        assert_eq!(0.0, stats.pps());
+        for _ in 0..120 {
+            stats.tick();
+        }
+        let mut combo = Combo::new();
+        stats.add_clear(combo.update(Clear::new(0, false), false));
+        assert_eq!(2000, stats.elapsed_millis());
+        assert_eq!(0.5, stats.pps());
+    }
+}
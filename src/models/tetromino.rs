@@ -1,3 +1,5 @@
+pub mod ars;
+pub mod big;
 pub mod i;
 pub mod j;
 pub mod l;
@@ -6,17 +8,68 @@ pub mod s;
 pub mod t;
 pub mod z;
 
-use crate::models::block::{Block, Direction};
+use crate::models::{
+    block::{Block, Direction},
+    field::Field,
+};
 
 pub trait Tetromino {
     fn move_(&mut self, move_dir: MoveDirection);
     fn rotate(&mut self, rotate_dir: RotateDirection);
     fn dry_move(&self, move_dir: MoveDirection) -> Vec<Block>;
     fn dry_rotate(&self, rotate_dir: RotateDirection) -> Vec<Block>;
+    fn shift(&mut self, x: i32, y: i32);
+    fn kicks(&self, rotate_dir: RotateDirection) -> Vec<(i32, i32)>;
+    fn can_kick(&self, _rotate_dir: RotateDirection, _field: &Field) -> bool {
+        true
+    }
     fn blocks(&self) -> Vec<Block>;
+    fn kind(&self) -> TetrominoKind;
+    fn direction(&self) -> TetrominoDirection;
+    fn clone_box(&self) -> Box<dyn Tetromino>;
+    fn try_move(&mut self, move_dir: MoveDirection, field: &Field) -> bool {
+        if !field.is_vacant(&self.dry_move(move_dir)) {
+            return false;
+        }
+        self.move_(move_dir);
+        true
+    }
+    fn try_rotate(&mut self, rotate_dir: RotateDirection, field: &Field, kicks: bool) -> bool {
+        let blocks = self.dry_rotate(rotate_dir);
+        let mut offsets = self.kicks(rotate_dir);
+        if !kicks || !self.can_kick(rotate_dir, field) {
+            offsets.truncate(1);
+        }
+        for (x, y) in offsets {
+            let kicked = blocks.iter().map(|block| block.shift(x, y)).collect();
+            if field.is_vacant(&kicked) {
+                self.rotate(rotate_dir);
+                self.shift(x, y);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Clone for Box<dyn Tetromino> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TetrominoKind {
+    I,
+    J,
+    L,
+    O,
+    S,
+    T,
+    Z,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum TetrominoDirection {
     Left,
     Up,
@@ -28,6 +81,34 @@ pub enum TetrominoDirection {
 pub enum RotateDirection {
     Left,
     Right,
+    Half,
+}
+
+impl TetrominoKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            TetrominoKind::I => "I",
+            TetrominoKind::J => "J",
+            TetrominoKind::L => "L",
+            TetrominoKind::O => "O",
+            TetrominoKind::S => "S",
+            TetrominoKind::T => "T",
+            TetrominoKind::Z => "Z",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "I" => Some(TetrominoKind::I),
+            "J" => Some(TetrominoKind::J),
+            "L" => Some(TetrominoKind::L),
+            "O" => Some(TetrominoKind::O),
+            "S" => Some(TetrominoKind::S),
+            "T" => Some(TetrominoKind::T),
+            "Z" => Some(TetrominoKind::Z),
+            _ => None,
+        }
+    }
 }
 
 impl TetrominoDirection {
@@ -45,6 +126,27 @@ impl TetrominoDirection {
                 TetrominoDirection::Right => TetrominoDirection::Down,
                 TetrominoDirection::Up => TetrominoDirection::Right,
             },
+            RotateDirection::Half => match self {
+                TetrominoDirection::Left => TetrominoDirection::Right,
+                TetrominoDirection::Down => TetrominoDirection::Up,
+                TetrominoDirection::Right => TetrominoDirection::Left,
+                TetrominoDirection::Up => TetrominoDirection::Down,
+            },
+        }
+    }
+
+    // SRS+ 180 kicks. Quarter turns are not kicked.
+    fn kicks(&self, rotate_dir: RotateDirection) -> Vec<(i32, i32)> {
+        match rotate_dir {
+            RotateDirection::Left | RotateDirection::Right => vec![(0, 0)],
+            RotateDirection::Half => match self {
+                TetrominoDirection::Right => vec![(0, 0), (0, 1), (1, 1), (-1, 1), (1, 0), (-1, 0)],
+                TetrominoDirection::Down => vec![(0, 0), (1, 0), (1, 2), (1, 1), (0, 2), (0, 1)],
+                TetrominoDirection::Left => {
+                    vec![(0, 0), (0, -1), (-1, -1), (1, -1), (-1, 0), (1, 0)]
+                }
+                TetrominoDirection::Up => vec![(0, 0), (-1, 0), (-1, 2), (-1, 1), (0, 2), (0, 1)],
+            },
         }
     }
 }
@@ -65,3 +167,47 @@ impl From<MoveDirection> for Direction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        for kind in [TetrominoKind::I, TetrominoKind::S, TetrominoKind::Z].iter() {
+            assert_eq!(Some(*kind), TetrominoKind::parse(kind.name()));
+        }
+        assert_eq!(None, TetrominoKind::parse("G"));
+    }
+
+    #[test]
+    fn test_rotate_half() {
+        let dirs = [
+            TetrominoDirection::Left,
+            TetrominoDirection::Up,
+            TetrominoDirection::Right,
+            TetrominoDirection::Down,
+        ];
+        for dir in dirs.iter() {
+            assert_eq!(
+                dir.rotate(RotateDirection::Right)
+                    .rotate(RotateDirection::Right),
+                dir.rotate(RotateDirection::Half)
+            );
+            assert_eq!(
+                *dir,
+                dir.rotate(RotateDirection::Half)
+                    .rotate(RotateDirection::Half)
+            );
+        }
+    }
+
+    #[test]
+    fn test_kicks() {
+        let dir = TetrominoDirection::Right;
+        assert_eq!(vec![(0, 0)], dir.kicks(RotateDirection::Left));
+        assert_eq!(vec![(0, 0)], dir.kicks(RotateDirection::Right));
+        assert_eq!(6, dir.kicks(RotateDirection::Half).len());
+        assert_eq!((0, 0), dir.kicks(RotateDirection::Half)[0]);
+    }
+}
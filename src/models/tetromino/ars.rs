@@ -0,0 +1,199 @@
+use crate::models::{
+    block::{Block, Color, Direction},
+    field::Field,
+    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection, TetrominoKind},
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ars {
+    kind: TetrominoKind,
+    dir: TetrominoDirection,
+    axis: Block,
+}
+
+impl Tetromino for Ars {
+    fn move_(&mut self, move_dir: MoveDirection) {
+        let dir = Direction::from(move_dir);
+        self.set_axis(self.axis().move_(dir));
+    }
+    fn rotate(&mut self, rotate_dir: RotateDirection) {
+        let dir = self.dir().rotate(rotate_dir);
+        self.set_dir(dir);
+    }
+    fn dry_move(&self, move_dir: MoveDirection) -> Vec<Block> {
+        let dir = Direction::from(move_dir);
+        Self::new(self.kind, *self.dir(), self.axis().move_(dir)).blocks()
+    }
+    fn dry_rotate(&self, rotate_dir: RotateDirection) -> Vec<Block> {
+        let dir = self.dir().rotate(rotate_dir);
+        Self::new(self.kind, dir, *self.axis()).blocks()
+    }
+    fn shift(&mut self, x: i32, y: i32) {
+        self.set_axis(self.axis().shift(x, y));
+    }
+    fn kicks(&self, _rotate_dir: RotateDirection) -> Vec<(i32, i32)> {
+        match self.kind {
+            TetrominoKind::I | TetrominoKind::O => vec![(0, 0)],
+            _ => vec![(0, 0), (1, 0), (-1, 0)],
+        }
+    }
+    fn can_kick(&self, rotate_dir: RotateDirection, field: &Field) -> bool {
+        match self.kind {
+            TetrominoKind::J | TetrominoKind::L | TetrominoKind::T => {
+                let mut blocks = self.dry_rotate(rotate_dir);
+                blocks.sort_by_key(|block| (-block.y(), *block.x()));
+                match blocks
+                    .into_iter()
+                    .find(|block| !field.is_vacant(&vec![*block]))
+                {
+                    Some(block) => block.x() != self.axis().x(),
+                    None => true,
+                }
+            }
+            _ => true,
+        }
+    }
+    fn blocks(&self) -> Vec<Block> {
+        self.cells()
+            .iter()
+            .map(|(x, y)| self.axis().shift(*x, *y))
+            .collect()
+    }
+    fn kind(&self) -> TetrominoKind {
+        self.kind
+    }
+    fn direction(&self) -> TetrominoDirection {
+        *self.dir()
+    }
+    fn clone_box(&self) -> Box<dyn Tetromino> {
+        Box::new(*self)
+    }
+}
+
+impl Ars {
+    pub fn new(kind: TetrominoKind, dir: TetrominoDirection, axis: Block) -> Self {
+        Self { kind, dir, axis }
+    }
+
+    pub fn spawn(kind: TetrominoKind) -> Self {
+        let color = match kind {
+            TetrominoKind::I => Color::Cyan,
+            TetrominoKind::J => Color::Blue,
+            TetrominoKind::L => Color::Orange,
+            TetrominoKind::O => Color::Yellow,
+            TetrominoKind::S => Color::Green,
+            TetrominoKind::T => Color::Purple,
+            TetrominoKind::Z => Color::Red,
+        };
+        let y = if kind == TetrominoKind::I { 20 } else { 21 };
+        Self::new(kind, TetrominoDirection::Right, Block::new(color, 4, y))
+    }
+}
+
+impl Ars {
+    fn dir(&self) -> &TetrominoDirection {
+        &self.dir
+    }
+    fn axis(&self) -> &Block {
+        &self.axis
+    }
+    fn set_dir(&mut self, dir: TetrominoDirection) {
+        self.dir = dir;
+    }
+    fn set_axis(&mut self, axis: Block) {
+        self.axis = axis;
+    }
+    fn cells(&self) -> [(i32, i32); 4] {
+        match (self.kind, self.dir()) {
+            (TetrominoKind::I, TetrominoDirection::Right)
+            | (TetrominoKind::I, TetrominoDirection::Left) => [(-1, 0), (0, 0), (1, 0), (2, 0)],
+            (TetrominoKind::I, _) => [(1, 1), (1, 0), (1, -1), (1, -2)],
+            (TetrominoKind::O, _) => [(0, 0), (1, 0), (0, -1), (1, -1)],
+            (TetrominoKind::S, TetrominoDirection::Right)
+            | (TetrominoKind::S, TetrominoDirection::Left) => [(0, 0), (1, 0), (-1, -1), (0, -1)],
+            (TetrominoKind::S, _) => [(-1, 1), (-1, 0), (0, 0), (0, -1)],
+            (TetrominoKind::Z, TetrominoDirection::Right)
+            | (TetrominoKind::Z, TetrominoDirection::Left) => [(-1, 0), (0, 0), (0, -1), (1, -1)],
+            (TetrominoKind::Z, _) => [(1, 1), (0, 0), (1, 0), (0, -1)],
+            (TetrominoKind::T, TetrominoDirection::Right) => [(-1, 0), (0, 0), (1, 0), (0, -1)],
+            (TetrominoKind::T, TetrominoDirection::Down) => [(0, 1), (-1, 0), (0, 0), (0, -1)],
+            (TetrominoKind::T, TetrominoDirection::Left) => [(0, 0), (-1, -1), (0, -1), (1, -1)],
+            (TetrominoKind::T, TetrominoDirection::Up) => [(0, 1), (0, 0), (1, 0), (0, -1)],
+            (TetrominoKind::J, TetrominoDirection::Right) => [(-1, 0), (0, 0), (1, 0), (1, -1)],
+            (TetrominoKind::J, TetrominoDirection::Down) => [(0, 1), (0, 0), (-1, -1), (0, -1)],
+            (TetrominoKind::J, TetrominoDirection::Left) => [(-1, 0), (-1, -1), (0, -1), (1, -1)],
+            (TetrominoKind::J, TetrominoDirection::Up) => [(0, 1), (1, 1), (0, 0), (0, -1)],
+            (TetrominoKind::L, TetrominoDirection::Right) => [(-1, 0), (0, 0), (1, 0), (-1, -1)],
+            (TetrominoKind::L, TetrominoDirection::Down) => [(-1, 1), (0, 1), (0, 0), (0, -1)],
+            (TetrominoKind::L, TetrominoDirection::Left) => [(1, 0), (-1, -1), (0, -1), (1, -1)],
+            (TetrominoKind::L, TetrominoDirection::Up) => [(0, 1), (0, 0), (0, -1), (1, -1)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate() {
+        let mut t = build_ars(TetrominoKind::T);
+        assert_eq!(
+            vec![
+                Block::new(Color::Purple, -1, 0),
+                Block::new(Color::Purple, 0, 0),
+                Block::new(Color::Purple, 1, 0),
+                Block::new(Color::Purple, 0, -1),
+            ],
+            t.blocks()
+        );
+        t.rotate(RotateDirection::Right);
+        t.rotate(RotateDirection::Right);
+        assert_eq!(
+            vec![
+                Block::new(Color::Purple, 0, 0),
+                Block::new(Color::Purple, -1, -1),
+                Block::new(Color::Purple, 0, -1),
+                Block::new(Color::Purple, 1, -1),
+            ],
+            t.blocks()
+        );
+    }
+
+    #[test]
+    fn test_kicks() {
+        assert_eq!(
+            vec![(0, 0)],
+            build_ars(TetrominoKind::I).kicks(RotateDirection::Right)
+        );
+        assert_eq!(
+            vec![(0, 0), (1, 0), (-1, 0)],
+            build_ars(TetrominoKind::S).kicks(RotateDirection::Right)
+        );
+    }
+
+    #[test]
+    fn test_can_kick() {
+        let t = Ars::new(
+            TetrominoKind::T,
+            TetrominoDirection::Right,
+            Block::new(Color::Purple, 1, 1),
+        );
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        assert!(t.can_kick(RotateDirection::Right, &field));
+        field.fix_blocks(vec![Block::new(Color::Grey, 1, 2)]);
+        assert!(!t.can_kick(RotateDirection::Right, &field));
+
+        let mut field = Field::new(vec![vec![None; 10]; 24]);
+        field.fix_blocks(vec![Block::new(Color::Grey, 0, 1)]);
+        assert!(t.can_kick(RotateDirection::Right, &field));
+    }
+
+    fn build_ars(kind: TetrominoKind) -> Ars {
+        Ars::new(
+            kind,
+            TetrominoDirection::Right,
+            Block::new(*Ars::spawn(kind).axis().color(), 0, 0),
+        )
+    }
+}
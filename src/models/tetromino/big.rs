@@ -0,0 +1,124 @@
+use crate::models::{
+    block::Block,
+    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection, TetrominoKind},
+};
+
+pub const SCALE: i32 = 2;
+
+pub struct Big {
+    tetromino: Box<dyn Tetromino>,
+    offset: (i32, i32),
+}
+
+impl Tetromino for Big {
+    fn move_(&mut self, move_dir: MoveDirection) {
+        match move_dir {
+            MoveDirection::Down => self.offset.1 -= 1,
+            _ => self.tetromino.move_(move_dir),
+        }
+    }
+    fn rotate(&mut self, rotate_dir: RotateDirection) {
+        self.tetromino.rotate(rotate_dir);
+    }
+    fn dry_move(&self, move_dir: MoveDirection) -> Vec<Block> {
+        match move_dir {
+            MoveDirection::Down => self
+                .blocks()
+                .iter()
+                .map(|block| block.shift(0, -1))
+                .collect(),
+            _ => self.scale(&self.tetromino.dry_move(move_dir)),
+        }
+    }
+    fn dry_rotate(&self, rotate_dir: RotateDirection) -> Vec<Block> {
+        self.scale(&self.tetromino.dry_rotate(rotate_dir))
+    }
+    fn shift(&mut self, x: i32, y: i32) {
+        self.offset = (self.offset.0 + x, self.offset.1 + y);
+    }
+    fn kicks(&self, rotate_dir: RotateDirection) -> Vec<(i32, i32)> {
+        self.tetromino
+            .kicks(rotate_dir)
+            .iter()
+            .map(|(x, y)| (x * SCALE, y * SCALE))
+            .collect()
+    }
+    fn blocks(&self) -> Vec<Block> {
+        self.scale(&self.tetromino.blocks())
+    }
+    fn kind(&self) -> TetrominoKind {
+        self.tetromino.kind()
+    }
+    fn direction(&self) -> TetrominoDirection {
+        self.tetromino.direction()
+    }
+    fn clone_box(&self) -> Box<dyn Tetromino> {
+        Box::new(Self {
+            tetromino: self.tetromino.clone_box(),
+            offset: self.offset,
+        })
+    }
+}
+
+impl Big {
+    pub fn new(mut tetromino: Box<dyn Tetromino>) -> Self {
+        let blocks = tetromino.blocks();
+        let min_x = blocks.iter().map(|block| *block.x()).min().unwrap_or(0);
+        let max_x = blocks.iter().map(|block| *block.x()).max().unwrap_or(0);
+        let max_y = blocks.iter().map(|block| *block.y()).max().unwrap_or(0);
+        let width = 10 / SCALE;
+        let top = 24 / SCALE - 1;
+        tetromino.shift((width - max_x + min_x) / 2 - min_x, top - max_y);
+        Self {
+            tetromino,
+            offset: (0, 0),
+        }
+    }
+}
+
+impl Big {
+    fn scale(&self, blocks: &[Block]) -> Vec<Block> {
+        let mut scaled = Vec::new();
+        for block in blocks.iter() {
+            for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)].iter() {
+                scaled.push(Block::new(
+                    *block.color(),
+                    block.x() * SCALE + x + self.offset.0,
+                    block.y() * SCALE + y + self.offset.1,
+                ));
+            }
+        }
+        scaled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{block::Color, tetromino_factory::TetrominoFactory};
+
+    #[test]
+    fn test_new() {
+        for kind in [TetrominoKind::I, TetrominoKind::O, TetrominoKind::T].iter() {
+            let big = Big::new(TetrominoFactory::build_tetromino(*kind));
+            let blocks = big.blocks();
+            assert_eq!(16, blocks.len());
+            assert!(blocks.iter().all(|block| (0..10).contains(block.x())));
+            assert_eq!(23, blocks.iter().map(|block| *block.y()).max().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_move() {
+        let mut big = Big::new(TetrominoFactory::build_tetromino(TetrominoKind::O));
+        assert!(big.blocks().contains(&Block::new(Color::Yellow, 4, 20)));
+        big.move_(MoveDirection::Left);
+        assert!(big.blocks().contains(&Block::new(Color::Yellow, 2, 20)));
+        assert_eq!(big.dry_move(MoveDirection::Down), {
+            big.move_(MoveDirection::Down);
+            big.blocks()
+        });
+        assert!(big.blocks().contains(&Block::new(Color::Yellow, 2, 19)));
+        assert_eq!(vec![(0, 0)], big.kicks(RotateDirection::Left));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::models::{
     block::{Block, Direction},
-    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection},
+    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection, TetrominoKind},
 };
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -29,6 +29,12 @@ impl Tetromino for I {
         let dir = self.dir().rotate(rotate_dir);
         Self::new(dir, axis).blocks()
     }
+    fn shift(&mut self, x: i32, y: i32) {
+        self.set_axis(self.axis().shift(x, y));
+    }
+    fn kicks(&self, rotate_dir: RotateDirection) -> Vec<(i32, i32)> {
+        self.dir().kicks(rotate_dir)
+    }
     fn blocks(&self) -> Vec<Block> {
         match self.dir() {
             TetrominoDirection::Left => vec![
@@ -57,6 +63,15 @@ impl Tetromino for I {
             ],
         }
     }
+    fn kind(&self) -> TetrominoKind {
+        TetrominoKind::I
+    }
+    fn direction(&self) -> TetrominoDirection {
+        *self.dir()
+    }
+    fn clone_box(&self) -> Box<dyn Tetromino> {
+        Box::new(*self)
+    }
 }
 
 impl I {
@@ -92,6 +107,11 @@ impl I {
                 TetrominoDirection::Right => self.axis().move_(Direction::Down),
                 TetrominoDirection::Down => self.axis().move_(Direction::Left),
             },
+            RotateDirection::Half => Self::new(
+                self.dir().rotate(RotateDirection::Right),
+                self.rotate_axis(RotateDirection::Right),
+            )
+            .rotate_axis(RotateDirection::Right),
         }
     }
 }
@@ -166,6 +186,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rotate_half() {
+        let mut i = build_i_tetromino();
+        i.rotate(RotateDirection::Half);
+        assert_eq!(
+            I::new(TetrominoDirection::Left, Block::new(Color::Cyan, -1, -1)),
+            i
+        );
+        i.rotate(RotateDirection::Half);
+        assert_eq!(
+            I::new(TetrominoDirection::Right, Block::new(Color::Cyan, 0, 0)),
+            i
+        );
+        i.rotate(RotateDirection::Right);
+        i.rotate(RotateDirection::Half);
+        assert_eq!(
+            I::new(TetrominoDirection::Up, Block::new(Color::Cyan, -1, 0)),
+            i
+        );
+    }
+
     #[test]
     fn test_dry_move() {
         let mut i = build_i_tetromino();
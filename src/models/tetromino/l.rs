@@ -1,6 +1,6 @@
 use crate::models::{
     block::{Block, Direction},
-    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection},
+    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection, TetrominoKind},
 };
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -26,6 +26,12 @@ impl Tetromino for L {
         let dir = self.dir().rotate(rotate_dir);
         Self::new(dir, *self.axis()).blocks()
     }
+    fn shift(&mut self, x: i32, y: i32) {
+        self.set_axis(self.axis().shift(x, y));
+    }
+    fn kicks(&self, rotate_dir: RotateDirection) -> Vec<(i32, i32)> {
+        self.dir().kicks(rotate_dir)
+    }
     fn blocks(&self) -> Vec<Block> {
         match self.dir() {
             TetrominoDirection::Left => vec![
@@ -54,6 +60,15 @@ impl Tetromino for L {
             ],
         }
     }
+    fn kind(&self) -> TetrominoKind {
+        TetrominoKind::L
+    }
+    fn direction(&self) -> TetrominoDirection {
+        *self.dir()
+    }
+    fn clone_box(&self) -> Box<dyn Tetromino> {
+        Box::new(*self)
+    }
 }
 
 impl L {
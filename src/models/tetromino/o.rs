@@ -1,6 +1,6 @@
 use crate::models::{
     block::{Block, Direction},
-    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection},
+    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection, TetrominoKind},
 };
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -26,6 +26,12 @@ impl Tetromino for O {
         let dir = self.dir().rotate(rotate_dir);
         Self::new(dir, *self.axis()).blocks()
     }
+    fn shift(&mut self, x: i32, y: i32) {
+        self.set_axis(self.axis().shift(x, y));
+    }
+    fn kicks(&self, _rotate_dir: RotateDirection) -> Vec<(i32, i32)> {
+        vec![(0, 0)]
+    }
     fn blocks(&self) -> Vec<Block> {
         vec![
             *self.axis(),
@@ -34,6 +40,15 @@ impl Tetromino for O {
             self.axis().move_(Direction::Right).move_(Direction::Up),
         ]
     }
+    fn kind(&self) -> TetrominoKind {
+        TetrominoKind::O
+    }
+    fn direction(&self) -> TetrominoDirection {
+        *self.dir()
+    }
+    fn clone_box(&self) -> Box<dyn Tetromino> {
+        Box::new(*self)
+    }
 }
 
 impl O {
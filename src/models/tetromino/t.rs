@@ -1,6 +1,6 @@
 use crate::models::{
     block::{Block, Direction},
-    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection},
+    tetromino::{MoveDirection, RotateDirection, Tetromino, TetrominoDirection, TetrominoKind},
 };
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -26,6 +26,12 @@ impl Tetromino for T {
         let dir = self.dir().rotate(rotate_dir);
         Self::new(dir, *self.axis()).blocks()
     }
+    fn shift(&mut self, x: i32, y: i32) {
+        self.set_axis(self.axis().shift(x, y));
+    }
+    fn kicks(&self, rotate_dir: RotateDirection) -> Vec<(i32, i32)> {
+        self.dir().kicks(rotate_dir)
+    }
     fn blocks(&self) -> Vec<Block> {
         match self.dir() {
             TetrominoDirection::Left => vec![
@@ -54,6 +60,15 @@ impl Tetromino for T {
             ],
         }
     }
+    fn kind(&self) -> TetrominoKind {
+        TetrominoKind::T
+    }
+    fn direction(&self) -> TetrominoDirection {
+        *self.dir()
+    }
+    fn clone_box(&self) -> Box<dyn Tetromino> {
+        Box::new(*self)
+    }
 }
 
 impl T {
@@ -147,6 +162,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rotate_half() {
+        let mut t = build_t_tetromino();
+        t.rotate(RotateDirection::Half);
+        assert_eq!(
+            T::new(TetrominoDirection::Left, Block::new(Color::Purple, 0, 0)),
+            t
+        );
+        t.rotate(RotateDirection::Half);
+        assert_eq!(
+            T::new(TetrominoDirection::Right, Block::new(Color::Purple, 0, 0)),
+            t
+        );
+    }
+
+    #[test]
+    fn test_shift() {
+        let mut t = build_t_tetromino();
+        t.shift(-1, 2);
+        assert_eq!(
+            T::new(TetrominoDirection::Right, Block::new(Color::Purple, -1, 2)),
+            t
+        );
+    }
+
     #[test]
     fn test_dry_move() {
         let mut t = build_t_tetromino();
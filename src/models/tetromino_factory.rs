@@ -1,32 +1,157 @@
 use crate::models::{
     block::{Block, Color},
-    tetromino::{i::I, j::J, l::L, o::O, s::S, t::T, z::Z, Tetromino, TetrominoDirection},
+    rng::Rng,
+    rotation_system::RotationSystem,
+    rules::Rules,
+    tetromino::{
+        big::Big, i::I, j::J, l::L, o::O, s::S, t::T, z::Z, Tetromino, TetrominoDirection,
+        TetrominoKind,
+    },
 };
 
+const NES_KINDS: [TetrominoKind; 7] = [
+    TetrominoKind::T,
+    TetrominoKind::J,
+    TetrominoKind::Z,
+    TetrominoKind::O,
+    TetrominoKind::S,
+    TetrominoKind::L,
+    TetrominoKind::I,
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Randomizer {
+    SevenBag,
+    SeededBag(u32),
+    Nes(u32),
+    Fixed,
+}
+
 pub struct TetrominoFactory {
-    seven_bag: Vec<Box<dyn Tetromino>>,
+    queue: Vec<Box<dyn Tetromino>>,
+    randomizer: Randomizer,
+    rotation_system: RotationSystem,
+    big: bool,
     rand: usize,
+    rng: Rng,
+    last: Option<TetrominoKind>,
 }
 
 impl TetrominoFactory {
     pub fn new() -> Self {
+        Self::with_rules(&Rules::modern())
+    }
+
+    pub fn with_rules(rules: &Rules) -> Self {
+        let randomizer = *rules.randomizer();
+        let seed = match randomizer {
+            Randomizer::Nes(seed) | Randomizer::SeededBag(seed) => seed,
+            _ => 0,
+        };
         Self {
-            seven_bag: Vec::new(),
+            queue: Vec::new(),
+            randomizer,
+            rotation_system: *rules.rotation_system(),
+            big: *rules.big(),
             rand: 0,
+            rng: Rng::new(seed),
+            last: None,
+        }
+    }
+
+    pub fn with_queue(rules: &Rules, kinds: &[TetrominoKind]) -> Self {
+        let mut tetromino_factory = Self::with_rules(rules);
+        tetromino_factory.randomizer = Randomizer::Fixed;
+        tetromino_factory.queue = kinds
+            .iter()
+            .rev()
+            .map(|kind| tetromino_factory.rotation_system.build_tetromino(*kind))
+            .collect();
+        tetromino_factory
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.randomizer == Randomizer::Fixed && self.queue.is_empty()
+    }
+
+    pub fn reset_bag(&mut self) {
+        if self.randomizer != Randomizer::Fixed {
+            self.queue.clear();
         }
     }
 
     pub fn pick_tetromino(&mut self) -> Box<dyn Tetromino> {
-        if let Some(tetromino) = self.seven_bag.pop() {
+        let tetromino = if let Some(tetromino) = self.queue.pop() {
             tetromino
         } else {
-            self.seven_bag = self.new_seven_bag();
-            self.seven_bag.pop().unwrap()
+            self.queue = self.generate();
+            self.queue.pop().unwrap()
+        };
+        if self.big {
+            Box::new(Big::new(tetromino))
+        } else {
+            tetromino
+        }
+    }
+
+    pub fn next_kinds(&mut self, num: usize) -> Vec<TetrominoKind> {
+        while self.queue.len() < num {
+            let mut queue = self.generate();
+            if queue.is_empty() {
+                break;
+            }
+            queue.append(&mut self.queue);
+            self.queue = queue;
+        }
+        self.queue
+            .iter()
+            .rev()
+            .take(num)
+            .map(|tetromino| tetromino.kind())
+            .collect()
+    }
+
+    pub fn build_tetromino(kind: TetrominoKind) -> Box<dyn Tetromino> {
+        match kind {
+            TetrominoKind::I => Box::new(Self::build_default_i()),
+            TetrominoKind::J => Box::new(Self::build_default_j()),
+            TetrominoKind::L => Box::new(Self::build_default_l()),
+            TetrominoKind::O => Box::new(Self::build_default_o()),
+            TetrominoKind::S => Box::new(Self::build_default_s()),
+            TetrominoKind::T => Box::new(Self::build_default_t()),
+            TetrominoKind::Z => Box::new(Self::build_default_z()),
+        }
+    }
+
+    fn generate(&mut self) -> Vec<Box<dyn Tetromino>> {
+        match self.randomizer {
+            Randomizer::SevenBag | Randomizer::SeededBag(_) => self.new_seven_bag(),
+            Randomizer::Nes(_) => {
+                let kind = self.nes_kind();
+                vec![self.rotation_system.build_tetromino(kind)]
+            }
+            Randomizer::Fixed => Vec::new(),
         }
     }
 
+    fn nes_kind(&mut self) -> TetrominoKind {
+        let mut roll = self.rng.below(8) as usize;
+        if roll == 7 || self.last == Some(NES_KINDS[roll]) {
+            roll = self.rng.below(7) as usize;
+        }
+        self.last = Some(NES_KINDS[roll]);
+        NES_KINDS[roll]
+    }
+
     fn new_seven_bag(&mut self) -> Vec<Box<dyn Tetromino>> {
-        self.fisher_yates_shuffle(&mut Self::build_seven_tetrominos())
+        let mut seven_tetrominos = match self.rotation_system {
+            RotationSystem::Srs => Self::build_seven_tetrominos(),
+            RotationSystem::Ars => NES_KINDS
+                .iter()
+                .map(|kind| self.rotation_system.build_tetromino(*kind))
+                .collect(),
+        };
+        self.fisher_yates_shuffle(&mut seven_tetrominos)
     }
 
     fn fisher_yates_shuffle(
@@ -35,8 +160,10 @@ impl TetrominoFactory {
     ) -> Vec<Box<dyn Tetromino>> {
         let mut ret = Vec::new();
         for i in (1..=7).rev() {
-            let rand = self.linear_congruential_generate();
-            let idx = rand % i;
+            let idx = match self.randomizer {
+                Randomizer::SeededBag(_) => self.rng.below(i as u32) as usize,
+                _ => self.linear_congruential_generate() % i,
+            };
             let removed = to_shuffle.remove(idx);
             ret.push(removed);
         }
@@ -91,3 +218,79 @@ impl TetrominoFactory {
         O::new(TetrominoDirection::Right, Block::new(Color::Yellow, 4, 20))
     }
 }
+
+impl Default for TetrominoFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_kinds() {
+        let mut tetromino_factory = TetrominoFactory::new();
+        let next = tetromino_factory.next_kinds(10);
+        assert_eq!(10, next.len());
+        for kind in next.iter() {
+            assert_eq!(*kind, tetromino_factory.pick_tetromino().kind());
+        }
+    }
+
+    #[test]
+    fn test_with_queue() {
+        let kinds = vec![TetrominoKind::T, TetrominoKind::I, TetrominoKind::O];
+        let mut tetromino_factory = TetrominoFactory::with_queue(&Rules::modern(), &kinds);
+        assert_eq!(kinds, tetromino_factory.next_kinds(5));
+        assert_eq!(TetrominoKind::T, tetromino_factory.pick_tetromino().kind());
+        assert_eq!(TetrominoKind::I, tetromino_factory.pick_tetromino().kind());
+        assert!(!tetromino_factory.is_empty());
+        assert_eq!(TetrominoKind::O, tetromino_factory.pick_tetromino().kind());
+        assert!(tetromino_factory.is_empty());
+        assert!(tetromino_factory.next_kinds(5).is_empty());
+    }
+
+    #[test]
+    fn test_reset_bag() {
+        let mut tetromino_factory = TetrominoFactory::new();
+        tetromino_factory.pick_tetromino();
+        tetromino_factory.reset_bag();
+        let mut bag = tetromino_factory.next_kinds(7);
+        bag.sort_by_key(|kind| *kind as usize);
+        bag.dedup();
+        assert_eq!(7, bag.len());
+
+        let kinds = vec![TetrominoKind::T];
+        let mut tetromino_factory = TetrominoFactory::with_queue(&Rules::modern(), &kinds);
+        tetromino_factory.reset_bag();
+        assert_eq!(kinds, tetromino_factory.next_kinds(1));
+    }
+
+    #[test]
+    fn test_seeded_bag() {
+        let rules = Rules::modern().with_randomizer(Randomizer::SeededBag(7));
+        let next = TetrominoFactory::with_rules(&rules).next_kinds(70);
+        assert_eq!(next, TetrominoFactory::with_rules(&rules).next_kinds(70));
+        for bag in next.chunks(7) {
+            let mut bag = bag.to_vec();
+            bag.sort_by_key(|kind| *kind as usize);
+            bag.dedup();
+            assert_eq!(7, bag.len());
+        }
+        let rules = Rules::modern().with_randomizer(Randomizer::SeededBag(8));
+        assert_ne!(next, TetrominoFactory::with_rules(&rules).next_kinds(70));
+    }
+
+    #[test]
+    fn test_nes_randomizer() {
+        let mut tetromino_factory = TetrominoFactory::with_rules(&Rules::classic(0, 42));
+        let next = tetromino_factory.next_kinds(1000);
+        let repeats = next.windows(2).filter(|pair| pair[0] == pair[1]).count();
+        assert!(repeats < 1000 / 7);
+        for kind in NES_KINDS.iter() {
+            assert!(next.contains(kind));
+        }
+    }
+}
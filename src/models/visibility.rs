@@ -0,0 +1,30 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Visibility {
+    Visible,
+    Fading(i32),
+    Invisible,
+}
+
+impl Visibility {
+    pub fn alpha(&self, age: i32) -> f64 {
+        match self {
+            Visibility::Visible => 1.0,
+            Visibility::Fading(frames) => (1.0 - age as f64 / *frames as f64).max(0.0),
+            Visibility::Invisible => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alpha() {
+        assert_eq!(1.0, Visibility::Visible.alpha(1000));
+        assert_eq!(1.0, Visibility::Fading(300).alpha(0));
+        assert_eq!(0.5, Visibility::Fading(300).alpha(150));
+        assert_eq!(0.0, Visibility::Fading(300).alpha(600));
+        assert_eq!(0.0, Visibility::Invisible.alpha(0));
+    }
+}
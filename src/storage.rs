@@ -0,0 +1,14 @@
+pub fn load(key: &str) -> Option<i32> {
+    let storage = web_sys::window()?.local_storage().ok()??;
+    storage.get_item(&build_key(key)).ok()??.parse().ok()
+}
+
+pub fn save(key: &str, value: i32) {
+    if let Some(Ok(Some(storage))) = web_sys::window().map(|window| window.local_storage()) {
+        let _ = storage.set_item(&build_key(key), &value.to_string());
+    }
+}
+
+fn build_key(key: &str) -> String {
+    format!("kurenai_tetris.{}", key)
+}
@@ -0,0 +1,236 @@
+use crate::models::{
+    block::Color,
+    field::Field,
+    game::Game,
+    input::{Action, Input},
+    move_generator::{MoveGenerator, Placement},
+    tetromino::TetrominoKind,
+};
+
+use serde_json::{json, Value};
+
+const BOARD_HEIGHT: i32 = 40;
+
+pub struct Frontend {
+    game: Game,
+    generator: MoveGenerator,
+}
+
+impl Frontend {
+    pub fn new(game: Game) -> Self {
+        let generator = MoveGenerator::new(*game.rules());
+        Self { game, generator }
+    }
+
+    pub fn rules(&self) -> Value {
+        json!({ "type": "rules" })
+    }
+
+    pub fn start(&mut self) -> Value {
+        let mut queue = vec![self.game.tetromino().kind().name()];
+        queue.extend(self.next_kinds().into_iter().map(TetrominoKind::name));
+        json!({
+            "type": "start",
+            "hold": self.game.hold().tetromino().map(|kind| kind.name()),
+            "queue": queue,
+            "combo": 0,
+            "back_to_back": false,
+            "board": Self::board(self.game.field()),
+        })
+    }
+
+    pub fn suggest(&self) -> Value {
+        json!({ "type": "suggest" })
+    }
+
+    pub fn stop(&self) -> Value {
+        json!({ "type": "stop" })
+    }
+
+    pub fn quit(&self) -> Value {
+        json!({ "type": "quit" })
+    }
+
+    // Plays the first suggested move that is reachable and returns the messages for the bot.
+    pub fn play(&mut self, suggestion: &Value) -> Result<Vec<Value>, String> {
+        let moves = match suggestion["moves"].as_array() {
+            Some(moves) => moves,
+            None => return Err(format!("expected a suggestion, got {}", suggestion)),
+        };
+        let (location, held, placement) = moves
+            .iter()
+            .find_map(|move_| {
+                let location = &move_["location"];
+                self.find_placement(location)
+                    .map(|(held, placement)| (move_.clone(), held, placement))
+            })
+            .ok_or("no suggested move is reachable")?;
+
+        let consumed = if held && self.game.hold().tetromino().is_none() {
+            2
+        } else {
+            1
+        };
+        if held {
+            self.press(Action::Hold);
+        }
+        for action in placement.path() {
+            self.press(*action);
+        }
+        while !self.game.phase().is_falling() && !self.game.phase().is_finished() {
+            self.game.update();
+        }
+
+        let mut messages = vec![json!({ "type": "play", "move": location })];
+        let next = self.next_kinds();
+        for kind in next.iter().skip(next.len().saturating_sub(consumed)) {
+            messages.push(json!({ "type": "new_piece", "piece": kind.name() }));
+        }
+        Ok(messages)
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn location_blocks(location: &Value) -> Option<Vec<(i32, i32)>> {
+        let kind = TetrominoKind::parse(location["type"].as_str()?)?;
+        let x = location["x"].as_i64()? as i32;
+        let y = location["y"].as_i64()? as i32;
+        let cells = match kind {
+            TetrominoKind::I => [(-1, 0), (0, 0), (1, 0), (2, 0)],
+            TetrominoKind::O => [(0, 0), (1, 0), (0, 1), (1, 1)],
+            TetrominoKind::T => [(-1, 0), (0, 0), (1, 0), (0, 1)],
+            TetrominoKind::L => [(-1, 0), (0, 0), (1, 0), (1, 1)],
+            TetrominoKind::J => [(-1, 0), (0, 0), (1, 0), (-1, 1)],
+            TetrominoKind::S => [(-1, 0), (0, 0), (0, 1), (1, 1)],
+            TetrominoKind::Z => [(-1, 1), (0, 1), (0, 0), (1, 0)],
+        };
+        let orientation = location["orientation"].as_str()?;
+        let mut blocks = Vec::new();
+        for (cx, cy) in cells.iter() {
+            let (cx, cy) = match orientation {
+                "north" => (*cx, *cy),
+                "east" => (*cy, -cx),
+                "south" => (-cx, -cy),
+                "west" => (-cy, *cx),
+                _ => return None,
+            };
+            blocks.push((x + cx, y + cy));
+        }
+        blocks.sort_by_key(|(x, y)| (*y, *x));
+        Some(blocks)
+    }
+
+    pub fn board(field: &Field) -> Value {
+        let mut board = vec![vec![Value::Null; 10]; BOARD_HEIGHT as usize];
+        for block in field.blocks() {
+            let name = match block.color() {
+                Color::Cyan => "I",
+                Color::Blue => "J",
+                Color::Orange => "L",
+                Color::Yellow => "O",
+                Color::Green => "S",
+                Color::Purple => "T",
+                Color::Red => "Z",
+                Color::Grey => "G",
+            };
+            board[*block.y() as usize][*block.x() as usize] = json!(name);
+        }
+        json!(board)
+    }
+}
+
+impl Frontend {
+    fn next_kinds(&mut self) -> Vec<TetrominoKind> {
+        let preview = *self.game.rules().preview();
+        self.game.next_kinds(preview)
+    }
+
+    fn find_placement(&mut self, location: &Value) -> Option<(bool, Placement)> {
+        let blocks = Self::location_blocks(location)?;
+        let kind = TetrominoKind::parse(location["type"].as_str()?)?;
+        let held = kind != self.game.tetromino().kind();
+        let tetromino = if !held {
+            self.game.tetromino().clone_box()
+        } else {
+            let swapped = match self.game.hold().tetromino() {
+                Some(held) => *held,
+                None => *self.next_kinds().first()?,
+            };
+            if swapped != kind || !*self.game.rules().hold() || !self.game.hold().can_hold() {
+                return None;
+            }
+            self.game.rules().build_tetromino(kind)
+        };
+        self.generator
+            .generate(self.game.field(), tetromino.as_ref())
+            .into_iter()
+            .find(|placement| {
+                let positions: Vec<_> = placement
+                    .blocks()
+                    .iter()
+                    .map(|block| (*block.x(), *block.y()))
+                    .collect();
+                positions == blocks
+            })
+            .map(|placement| (held, placement))
+    }
+
+    fn press(&mut self, action: Action) {
+        self.game.key_event(Input::new(vec![action]));
+        self.game.key_event(Input::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::game_mode::endless::Endless;
+
+    #[test]
+    fn test_location_blocks() {
+        let location = json!({ "type": "T", "orientation": "north", "x": 4, "y": 0 });
+        assert_eq!(
+            Some(vec![(3, 0), (4, 0), (5, 0), (4, 1)]),
+            Frontend::location_blocks(&location)
+        );
+        let location = json!({ "type": "I", "orientation": "east", "x": 0, "y": 2 });
+        assert_eq!(
+            Some(vec![(0, 0), (0, 1), (0, 2), (0, 3)]),
+            Frontend::location_blocks(&location)
+        );
+        let location = json!({ "type": "Q", "orientation": "north", "x": 0, "y": 0 });
+        assert_eq!(None, Frontend::location_blocks(&location));
+    }
+
+    #[test]
+    fn test_start() {
+        let mut frontend = Frontend::new(Game::new(Box::new(Endless)));
+        let start = frontend.start();
+        assert_eq!("start", start["type"]);
+        assert_eq!(6, start["queue"].as_array().unwrap().len());
+        assert_eq!(40, start["board"].as_array().unwrap().len());
+        assert_eq!(Value::Null, start["hold"]);
+    }
+
+    #[test]
+    fn test_play() {
+        let mut frontend = Frontend::new(Game::new(Box::new(Endless)));
+        let start = frontend.start();
+        let kind = start["queue"][0].clone();
+        let orientation = if kind == "I" { "east" } else { "north" };
+        let (x, y) = if kind == "I" { (0, 2) } else { (1, 0) };
+        let location = json!({ "type": kind, "orientation": orientation, "x": x, "y": y });
+        let suggestion = json!({ "type": "suggestion", "moves": [{ "location": location }] });
+        let messages = frontend.play(&suggestion).unwrap();
+        assert_eq!("play", messages[0]["type"]);
+        assert_eq!("new_piece", messages[1]["type"]);
+        assert_eq!(4, frontend.game().field().blocks().len());
+        assert!(frontend.game().phase().is_falling());
+
+        let location = json!({ "type": "T", "orientation": "north", "x": 4, "y": 30 });
+        let suggestion = json!({ "type": "suggestion", "moves": [{ "location": location }] });
+        assert!(frontend.play(&suggestion).is_err());
+    }
+}